@@ -1,141 +1,5558 @@
 //! Utility iterators.
 
-use num::traits::AsPrimitive;
+use num::traits::{AsPrimitive, PrimInt};
 use rand::{rngs::ThreadRng, thread_rng, Rng};
+use std::any::Any;
+use std::collections::VecDeque;
+use std::error::Error;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::iter::{Flatten, FusedIterator};
 use std::marker::PhantomData;
-use std::{mem, ops::Range};
+use std::num::ParseIntError;
+use std::ops::{Range, RangeInclusive};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{env, fmt, mem, thread};
 
 /// Returns a range iterator.
 ///
-///     seg=2,  lim=6: [0..3, 3..6]
-///     seg=2,  lim=7: [0..4, 4..7]
-///     seg=4, lim=10: [0..3, 3..6, 6..8, 8..10]
+/// ```text
+/// seg=2,  lim=6: [0..3, 3..6]
+/// seg=2,  lim=7: [0..4, 4..7]
+/// seg=4, lim=10: [0..3, 3..6, 6..8, 8..10]
+/// ```
 ///
 /// Ranges may have different lengths depending on the `lim % seg` remainder.
 ///
+/// Segment bounds are computed in closed form, so the iterator is
+/// double-ended and exact-size, and `nth` runs in constant time.
+///
 /// # Arguments
 ///
 /// * `seg` - The number of segments to divide the length.
 ///
 /// * `lim` - The total number of elements.
+///
+/// # Panics
+///
+/// Panics if `seg` is zero. Use [`try_rngs`] to validate inputs instead.
 pub fn rngs(seg: usize, lim: usize) -> RngItr {
-    RngItr {
-        idx: 0,
-        stp: lim.saturating_div(seg),
-        lim,
-        stp_adj: lim % seg,
+    try_rngs_with(seg, lim, SegPolicy::Clamp).expect("invalid rngs arguments")
+}
+
+/// Returns a range iterator, or an error if `seg` or `lim` is degenerate.
+///
+/// Equivalent to `try_rngs_with(seg, lim, SegPolicy::Error)`.
+///
+/// ```text
+/// seg=0, lim=6: Err(ZroSeg)
+/// seg=2, lim=0: Err(ZroLim)
+/// seg=4, lim=3: Err(SegGtLim { seg: 4, lim: 3 })
+/// ```
+pub fn try_rngs(seg: usize, lim: usize) -> Result<RngItr, RngsError> {
+    try_rngs_with(seg, lim, SegPolicy::Error)
+}
+
+/// Returns a range iterator, resolving a zero `lim` or a `seg` greater
+/// than `lim` with `pol`.
+///
+/// ```text
+/// seg=4, lim=3, Clamp:      [0..1, 1..2, 2..3]
+/// seg=4, lim=3, AllowEmpty: [0..1, 1..2, 2..3, 3..3]
+/// seg=2, lim=0, Clamp:      [0..0]
+/// seg=2, lim=0, AllowEmpty: [0..0, 0..0]
+/// ```
+///
+/// A zero `seg` is an error under every policy.
+///
+/// # Arguments
+///
+/// * `seg` - The number of segments to divide the length.
+///
+/// * `lim` - The total number of elements.
+///
+/// * `pol` - How degenerate inputs are resolved.
+pub fn try_rngs_with(seg: usize, lim: usize, pol: SegPolicy) -> Result<RngItr, RngsError> {
+    if seg == 0 {
+        return Err(RngsError::ZroSeg);
     }
+    let cnt = match pol {
+        SegPolicy::Clamp => seg.min(lim).max(1),
+        SegPolicy::AllowEmpty => seg,
+        SegPolicy::Error if lim == 0 => return Err(RngsError::ZroLim),
+        SegPolicy::Error if seg > lim => return Err(RngsError::SegGtLim { seg, lim }),
+        SegPolicy::Error => seg,
+    };
+    Ok(Partition::new(0, cnt, lim as u128).iter())
 }
 
-// A range iterator.
-#[derive(Debug, Clone)]
-pub struct RngItr {
-    idx: usize,
-    stp: usize,
-    lim: usize,
-    stp_adj: usize,
+/// How [`try_rngs_with`] resolves a zero `lim` or a `seg` greater than `lim`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SegPolicy {
+    /// Reduces the segment count to `lim`, as [`rngs`] does.
+    ///
+    /// A zero `lim` yields a single `0..0` segment.
+    Clamp,
+    /// Keeps all `seg` segments; segments past `lim` are empty.
+    AllowEmpty,
+    /// Returns [`RngsError::ZroLim`] or [`RngsError::SegGtLim`].
+    #[default]
+    Error,
 }
-impl Iterator for RngItr {
-    type Item = Range<usize>;
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.idx == usize::MAX {
-            None
-        } else {
-            let adj: usize = if self.stp_adj > 0 {
-                self.stp_adj -= 1;
-                1
-            } else {
-                0
-            };
-            let lim = (self.idx + self.stp + adj).min(self.lim);
-            let rng = Range {
-                start: self.idx,
-                end: lim,
-            };
-            if lim == self.lim {
-                self.idx = usize::MAX;
-            } else {
-                self.idx += self.stp + adj;
+
+/// An error returned when range iterator arguments are invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RngsError {
+    /// The segment count is zero.
+    ZroSeg,
+    /// The total number of elements is zero.
+    ZroLim,
+    /// The segment count is greater than the total number of elements.
+    SegGtLim { seg: usize, lim: usize },
+}
+impl fmt::Display for RngsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RngsError::ZroSeg => write!(f, "segment count is zero"),
+            RngsError::ZroLim => write!(f, "element count is zero"),
+            RngsError::SegGtLim { seg, lim } => {
+                write!(f, "segment count {seg} exceeds element count {lim}")
             }
-            Some(rng)
         }
     }
 }
+impl Error for RngsError {}
 
-/// Returns an iterator which generates random integers.
+/// Returns a range iterator with segments of about `len` elements.
 ///
-/// Generates equal quantities of integers represented
-/// by 1-byte, 2-bytes, up to n-bytes.
+/// ```text
+/// len=3, lim=10: [0..4, 4..7, 7..10]
+/// len=4, lim=10: [0..4, 4..7, 7..10]
+/// len=6, lim=10: [0..5, 5..10]
+/// len=8, lim=3:  [0..3]
+/// ```
 ///
-/// Generates an infinite number of integers.
-pub fn rnds_eql_byt<T>() -> RndEqlBytItr<T>
-where
-    T: AsPrimitive<T>,
-    usize: num::traits::AsPrimitive<T>,
-{
-    RndEqlBytItr {
-        rng: thread_rng(),
-        byt: 0,
-        phn: PhantomData,
+/// The segment count is `lim / len` rounded to the nearest integer, and
+/// at least one. Segment lengths then differ by at most one, as with
+/// [`rngs`], so there is no short trailing segment.
+///
+/// # Arguments
+///
+/// * `len` - The target number of elements in a segment.
+///
+/// * `lim` - The total number of elements.
+///
+/// # Panics
+///
+/// Panics if `len` is zero.
+pub fn rngs_by_len(len: usize, lim: usize) -> RngItr {
+    assert!(len != 0, "len must be non-zero");
+    // Round half up without overflowing `lim + len / 2`.
+    let seg = lim / len + usize::from(lim % len >= len - len / 2);
+    rngs(seg.max(1), lim)
+}
+
+/// Returns a range iterator with segments of about `len` elements, and
+/// between `min` and `max` elements where possible.
+///
+/// ```text
+/// len=3, min=3, max=8, lim=10: [0..4, 4..7, 7..10]
+/// len=2, min=3, max=8, lim=10: [0..4, 4..7, 7..10]
+/// len=9, min=3, max=8, lim=10: [0..5, 5..10]
+/// len=9, min=6, max=8, lim=10: [0..5, 5..10]
+/// ```
+///
+/// The segment count from [`rngs_by_len`] is adjusted to respect the
+/// bounds. When both cannot hold, `max` wins over `min`. A `lim` smaller
+/// than `min` yields a single segment.
+///
+/// # Arguments
+///
+/// * `len` - The target number of elements in a segment.
+///
+/// * `min` - The minimum number of elements in a segment.
+///
+/// * `max` - The maximum number of elements in a segment.
+///
+/// * `lim` - The total number of elements.
+///
+/// # Panics
+///
+/// Panics if `len` or `max` is zero, or if `min` is greater than `max`.
+pub fn rngs_by_len_bnd(len: usize, min: usize, max: usize, lim: usize) -> RngItr {
+    assert!(max != 0, "max must be non-zero");
+    assert!(min <= max, "min must not exceed max");
+    let seg = rngs_by_len(len, lim)
+        .len()
+        .min(lim.checked_div(min).unwrap_or(usize::MAX))
+        .max(lim.div_ceil(max));
+    rngs(seg.max(1), lim)
+}
+
+/// Returns a range iterator with at most `seg` segments of at least `min`
+/// elements.
+///
+/// ```text
+/// seg=64, min=10, lim=100: [0..10, 10..20, ..., 90..100]
+/// seg=4,  min=3,  lim=10:  [0..4, 4..7, 7..10]
+/// seg=4,  min=3,  lim=2:   [0..2]
+/// ```
+///
+/// Equivalent to `rngs(seg, lim).min_len(min)`. The iterator's length is
+/// the resulting segment count. See [`Partition::min_len`].
+///
+/// # Panics
+///
+/// Panics if `seg` is zero.
+pub fn rngs_min(seg: usize, min: usize, lim: usize) -> RngItr {
+    rngs(seg, lim).min_len(min)
+}
+
+/// Returns a range iterator over an offset range of any integer type.
+///
+/// ```text
+/// seg=2, rng=10..16:  [10..13, 13..16]
+/// seg=3, rng=-5i64..5: [-5..-1, -1..2, 2..5]
+/// seg=2, rng=0u8..255: [0..128, 128..255]
+/// ```
+///
+/// Segments follow [`rngs`] over the length of `rng`. An empty `rng`
+/// yields a single empty segment at its start.
+///
+/// # Arguments
+///
+/// * `seg` - The number of segments to divide the range.
+///
+/// * `rng` - The range of indexes.
+///
+/// # Panics
+///
+/// Panics if `seg` is zero.
+pub fn rngs_rng<I: Idx>(seg: usize, rng: Range<I>) -> RngItr<I> {
+    assert!(seg != 0, "seg must be non-zero");
+    let len = if rng.start < rng.end {
+        rng.start.dst(rng.end)
+    } else {
+        0
+    };
+    let cnt = (seg as u128).min(len).max(1) as usize;
+    Partition::new(rng.start, cnt, len).iter()
+}
+
+/// Returns an inclusive range iterator over an inclusive range of any
+/// integer type, including its full domain.
+///
+/// ```text
+/// seg=2, rng=0..=9:         [0..=4, 5..=9]
+/// seg=2, rng=0u64..=MAX:    [0..=2^63-1, 2^63..=MAX]
+/// seg=3, rng=i8::MIN..=MAX: [-128..=-43, -42..=42, 43..=127]
+/// ```
+///
+/// Segments follow [`rngs`] over the length of `rng`, and are computed
+/// without overflow even when the length exceeds the index type. An empty
+/// `rng` yields no segments.
+///
+/// # Arguments
+///
+/// * `seg` - The number of segments to divide the range.
+///
+/// * `rng` - The inclusive range of indexes.
+///
+/// # Panics
+///
+/// Panics if `seg` is zero.
+pub fn rngs_rng_inc<I: Idx>(seg: usize, rng: RangeInclusive<I>) -> RngIncItr<I> {
+    assert!(seg != 0, "seg must be non-zero");
+    let (lo, hi) = (*rng.start(), *rng.end());
+    if rng.is_empty() {
+        return RngIncItr {
+            itr: Partition {
+                off: lo,
+                cnt: 0,
+                stp: 0,
+                stp_adj: 0,
+                rem: RemainderPolicy::Front,
+            }
+            .iter(),
+        };
+    }
+    // The length is `lst + 1`, which may not fit in a u128.
+    let lst = lo.dst(hi);
+    let cnt = (seg as u128).min(lst.saturating_add(1)) as usize;
+    let (stp, rem) = (lst / cnt as u128, (lst % cnt as u128) as usize + 1);
+    // A single segment over a full u128 domain keeps `stp_adj == cnt`, as
+    // its length does not fit in `stp`.
+    let (stp, stp_adj) = if rem == cnt && cnt > 1 {
+        (stp + 1, 0)
+    } else {
+        (stp, rem)
+    };
+    RngIncItr {
+        itr: Partition {
+            off: lo,
+            cnt,
+            stp,
+            stp_adj,
+            rem: RemainderPolicy::Front,
+        }
+        .iter(),
     }
 }
-/// An iterator generating random integers.
-#[derive(Debug, Clone)]
-pub struct RndEqlBytItr<T>
+
+/// An integer index type which ranges can be partitioned over.
+///
+/// Implemented for all primitive integers. Distances between indexes are
+/// computed in `u128`, which holds the distance between any two values of
+/// a primitive integer type.
+pub trait Idx: Copy + PartialOrd {
+    /// Returns the distance from `self` up to `hi`.
+    fn dst(self, hi: Self) -> u128;
+
+    /// Returns `self` advanced by `dst`.
+    fn fwd(self, dst: u128) -> Self;
+}
+impl<T> Idx for T
 where
-    T: AsPrimitive<T>,
-    usize: num::traits::AsPrimitive<T>,
+    T: PrimInt + AsPrimitive<u128>,
+    u128: AsPrimitive<T>,
 {
-    rng: ThreadRng,
-    byt: usize,
-    phn: PhantomData<T>,
+    fn dst(self, hi: Self) -> u128 {
+        // Signed values sign-extend, so wrapping arithmetic stays exact.
+        hi.as_().wrapping_sub(self.as_())
+    }
+
+    fn fwd(self, dst: u128) -> Self {
+        self.as_().wrapping_add(dst).as_()
+    }
 }
 
-impl<T> Iterator for RndEqlBytItr<T>
-where
-    T: AsPrimitive<T>,
-    usize: num::traits::AsPrimitive<T>,
-{
-    type Item = T;
-    fn next(&mut self) -> Option<Self::Item> {
-        // Generate a random integer with `byt + 1` number of bytes.
+/// Returns a partition of `0..lim` into `seg` segments.
+///
+/// ```text
+/// seg=4, lim=10: [0..3, 3..6, 6..8, 8..10]
+/// ```
+///
+/// Segments follow [`rngs`], and are computed on demand rather than
+/// stored. Equivalent to `rngs(seg, lim).prt()`.
+///
+/// # Arguments
+///
+/// * `seg` - The number of segments to divide the length.
+///
+/// * `lim` - The total number of elements.
+///
+/// # Panics
+///
+/// Panics if `seg` is zero.
+pub fn prt(seg: usize, lim: usize) -> Partition {
+    rngs(seg, lim).prt()
+}
 
-        // Determine min inclusive integer.
-        let lo: usize = if self.byt == 0 {
-            0
+/// A partition of a range into segments.
+///
+/// Segment bounds are computed in closed form, so lookups in either
+/// direction run in constant time. [`RngItr`] iterates the segments.
+///
+/// With the `serde` feature, a partition serializes as its plan
+/// `{ off, lim, seg, rem }`: the start, the number of elements, the number
+/// of segments and the [`RemainderPolicy`], which defaults to `Front`.
+/// Plans that do not fit the index type are rejected. `lim` is a `u128`
+/// whatever its value, so formats without 128-bit integers cannot
+/// serialize a partition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Partition<I = usize> {
+    off: I,
+    cnt: usize,
+    stp: u128,
+    stp_adj: usize,
+    rem: RemainderPolicy,
+}
+impl<I: Idx> Partition<I> {
+    /// Returns a partition of `len` elements from `off` into `cnt` segments.
+    fn new(off: I, cnt: usize, len: u128) -> Self {
+        Partition {
+            off,
+            cnt,
+            stp: len / cnt as u128,
+            stp_adj: (len % cnt as u128) as usize,
+            rem: RemainderPolicy::Front,
+        }
+    }
+
+    /// Returns the partition with the remainder placed by `rem`.
+    ///
+    /// ```text
+    /// seg=4, lim=10, Front:  [0..3, 3..6, 6..8, 8..10]
+    /// seg=4, lim=10, Back:   [0..2, 2..4, 4..7, 7..10]
+    /// seg=4, lim=10, Spread: [0..2, 2..5, 5..7, 7..10]
+    /// seg=4, lim=10, Last:   [0..2, 2..4, 4..6, 6..10]
+    /// ```
+    pub fn rem_pol(self, rem: RemainderPolicy) -> Self {
+        Partition { rem, ..self }
+    }
+
+    /// Returns the partition with fewer segments where needed so that no
+    /// segment holds fewer than `min` elements.
+    ///
+    /// ```text
+    /// seg=64, lim=100, min=10: [0..10, 10..20, ..., 90..100]
+    /// seg=4,  lim=10,  min=3:  [0..4, 4..7, 7..10]
+    /// seg=4,  lim=2,   min=3:  [0..2]
+    /// ```
+    ///
+    /// The segment count becomes `lim / min` when that is smaller, and at
+    /// least one, so only a partition of fewer than `min` elements has a
+    /// shorter segment. [`Partition::len`] reports the resulting count.
+    pub fn min_len(self, min: usize) -> Self {
+        let len = self.bnd(self.cnt);
+        let cnt = (self.cnt as u128).min(len.checked_div(min as u128).unwrap_or(u128::MAX).max(1))
+            as usize;
+        if cnt == self.cnt {
+            self
         } else {
-            1 << (self.byt * 8)
+            Partition::new(self.off, cnt, len).rem_pol(self.rem)
+        }
+    }
+
+    /// Returns the start of segment `idx` relative to `off`.
+    ///
+    /// Segments hold `stp` elements, plus the `stp_adj` remainder elements
+    /// placed according to `rem`.
+    fn bnd(&self, idx: usize) -> u128 {
+        let (idx, cnt, adj) = (idx as u128, self.cnt as u128, self.stp_adj as u128);
+        idx * self.stp
+            + match self.rem {
+                RemainderPolicy::Front => idx.min(adj),
+                RemainderPolicy::Back => idx.saturating_sub(cnt - adj),
+                RemainderPolicy::Spread => idx * adj / cnt,
+                RemainderPolicy::Last if idx == cnt => adj,
+                RemainderPolicy::Last => 0,
+            }
+    }
+
+    /// Returns the number of remainder elements in segment `idx`.
+    fn xtr(&self, idx: usize) -> u128 {
+        let (idx, cnt, adj) = (idx as u128, self.cnt as u128, self.stp_adj as u128);
+        match self.rem {
+            RemainderPolicy::Front => u128::from(idx < adj),
+            RemainderPolicy::Back => u128::from(idx >= cnt - adj),
+            RemainderPolicy::Spread => (idx + 1) * adj / cnt - idx * adj / cnt,
+            RemainderPolicy::Last if idx + 1 == cnt => adj,
+            RemainderPolicy::Last => 0,
+        }
+    }
+
+    /// Returns the range of segment `idx`.
+    fn rng(&self, idx: usize) -> Range<I> {
+        self.off.fwd(self.bnd(idx))..self.off.fwd(self.bnd(idx + 1))
+    }
+
+    /// Returns the number of segments.
+    pub fn len(&self) -> usize {
+        self.cnt
+    }
+
+    /// Returns true if there are no segments.
+    pub fn is_empty(&self) -> bool {
+        self.cnt == 0
+    }
+
+    /// Returns the range of segment `idx`, or `None` if out of bounds.
+    ///
+    /// ```text
+    /// seg=4, lim=10, idx=2: Some(6..8)
+    /// seg=4, lim=10, idx=4: None
+    /// ```
+    pub fn get(&self, idx: usize) -> Option<Range<I>> {
+        (idx < self.cnt).then(|| self.rng(idx))
+    }
+
+    /// Returns true if `val` lies within a segment.
+    pub fn contains(&self, val: I) -> bool {
+        self.off <= val && self.off.dst(val) < self.bnd(self.cnt)
+    }
+
+    /// Returns the index of the segment containing `val`, or `None` if no
+    /// segment contains it.
+    ///
+    /// ```text
+    /// seg=4, lim=10, val=5:  Some(1)
+    /// seg=4, lim=10, val=6:  Some(2)
+    /// seg=4, lim=10, val=10: None
+    /// ```
+    ///
+    /// Empty segments never contain a value.
+    pub fn segment_of(&self, val: I) -> Option<usize> {
+        if !self.contains(val) {
+            return None;
+        }
+        let ofs = self.off.dst(val);
+        let (cnt, adj) = (self.cnt as u128, self.stp_adj as u128);
+        let idx = match self.rem {
+            RemainderPolicy::Front => {
+                // The first `stp_adj` segments are one element longer.
+                let lng = adj * (self.stp + 1);
+                if ofs < lng {
+                    ofs / (self.stp + 1)
+                } else {
+                    adj + (ofs - lng) / self.stp
+                }
+            }
+            RemainderPolicy::Back => {
+                // The last `stp_adj` segments are one element longer.
+                let shr = (cnt - adj) * self.stp;
+                if ofs < shr {
+                    ofs / self.stp
+                } else {
+                    cnt - adj + (ofs - shr) / (self.stp + 1)
+                }
+            }
+            RemainderPolicy::Spread => {
+                // Segment `idx` starts at `idx * tot / cnt` rounded down.
+                let tot = self.bnd(self.cnt);
+                match (ofs + 1).checked_mul(cnt) {
+                    Some(num) => (num - 1) / tot,
+                    None => {
+                        let (mut lo, mut hi) = (0, self.cnt);
+                        while hi - lo > 1 {
+                            let mid = lo + (hi - lo) / 2;
+                            if self.bnd(mid) <= ofs {
+                                lo = mid;
+                            } else {
+                                hi = mid;
+                            }
+                        }
+                        lo as u128
+                    }
+                }
+            }
+            RemainderPolicy::Last => ofs
+                .checked_div(self.stp)
+                .map_or(cnt - 1, |idx| idx.min(cnt - 1)),
         };
+        Some(idx as usize)
+    }
 
-        // Determine max inclusive integer.
-        // Use u128 to allow shifting (1<<64)-1 for 64-bit integer.
-        let hi_inc: usize = ((1u128 << ((self.byt + 1) * 8) as u128) - 1) as usize;
+    /// Returns an iterator over the segments.
+    pub fn iter(&self) -> RngItr<I> {
+        RngItr {
+            prt: *self,
+            idx: 0,
+            end: self.cnt,
+        }
+    }
+}
+impl<I: Idx> IntoIterator for Partition<I> {
+    type Item = Range<I>;
+    type IntoIter = RngItr<I>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<I: Idx> IntoIterator for &Partition<I> {
+    type Item = Range<I>;
+    type IntoIter = RngItr<I>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
 
-        // Generate the random integer.
-        let ret: usize = self.rng.gen_range(lo..=hi_inc);
+/// Where a partition places the `lim % seg` remainder elements.
+///
+/// Each policy is deterministic: segment `idx` of `seg` segments over
+/// `lim` elements starts at the offset given below, with `stp = lim / seg`
+/// and `adj = lim % seg`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RemainderPolicy {
+    /// The first `adj` segments are one element longer.
+    ///
+    /// Starts at `idx * stp + min(idx, adj)`, as numpy's `array_split`.
+    #[default]
+    Front,
+    /// The last `adj` segments are one element longer.
+    ///
+    /// Starts at `idx * stp + max(idx - (seg - adj), 0)`.
+    Back,
+    /// Longer segments are spread evenly.
+    ///
+    /// Starts at `idx * lim / seg` rounded down, as the MPI block
+    /// distribution `BLOCK_LOW`.
+    Spread,
+    /// The last segment holds the whole remainder.
+    ///
+    /// Starts at `idx * stp`.
+    Last,
+}
 
-        // Prepare for the next iteration.
-        self.byt = (self.byt + 1) % mem::size_of::<Self::Item>();
+/// A range iterator.
+///
+/// With the `serde` feature, an iterator serializes as the plan of its
+/// [`Partition`] with the remaining segment indexes,
+/// `{ off, lim, seg, rem, idx, end }`. `lim` is a `u128`, as in the plan.
+#[derive(Debug, Clone)]
+pub struct RngItr<I = usize> {
+    prt: Partition<I>,
+    idx: usize,
+    end: usize,
+}
+impl<I: Idx> RngItr<I> {
+    /// Returns the partition of all segments, including those already
+    /// iterated.
+    pub fn prt(&self) -> Partition<I> {
+        self.prt
+    }
 
-        Some(ret.as_())
+    /// Returns the iterator with the remainder placed by `rem`.
+    ///
+    /// See [`Partition::rem_pol`].
+    pub fn rem_pol(self, rem: RemainderPolicy) -> Self {
+        RngItr {
+            prt: self.prt.rem_pol(rem),
+            ..self
+        }
+    }
+
+    /// Returns an iterator from the first segment of the partition with no
+    /// segment shorter than `min` elements.
+    ///
+    /// See [`Partition::min_len`].
+    pub fn min_len(self, min: usize) -> Self {
+        self.prt.min_len(min).iter()
+    }
+
+    /// Returns the range of segment `idx`.
+    fn rng(&self, idx: usize) -> Range<I> {
+        self.prt.rng(idx)
+    }
+
+    /// Returns an iterator which pairs each segment with the segment
+    /// expanded by `lft` elements before and `rgt` elements after it.
+    ///
+    /// ```text
+    /// seg=3, lim=9, lft=1, rgt=2: [(0..3, 0..5), (3..6, 2..8), (6..9, 5..9)]
+    /// ```
+    ///
+    /// Expanded ranges are clamped to the span of the whole partition,
+    /// `0..lim`, including segments already iterated.
+    ///
+    /// # Arguments
+    ///
+    /// * `lft` - The number of context elements before each segment.
+    ///
+    /// * `rgt` - The number of context elements after each segment.
+    pub fn halo(self, lft: usize, rgt: usize) -> HaloItr<I> {
+        HaloItr {
+            lft: lft as u128,
+            rgt: rgt as u128,
+            itr: self,
+        }
+    }
+}
+impl<I: Idx> Iterator for RngItr<I> {
+    type Item = Range<I>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx == self.end {
+            None
+        } else {
+            let rng = self.rng(self.idx);
+            self.idx += 1;
+            Some(rng)
+        }
+    }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n < self.end - self.idx {
+            self.idx += n;
+            self.next()
+        } else {
+            self.idx = self.end;
+            None
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.idx;
+        (len, Some(len))
+    }
+    fn count(self) -> usize {
+        self.len()
+    }
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+impl<I: Idx> DoubleEndedIterator for RngItr<I> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx == self.end {
+            None
+        } else {
+            self.end -= 1;
+            Some(self.rng(self.end))
+        }
+    }
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if n < self.end - self.idx {
+            self.end -= n;
+            self.next_back()
+        } else {
+            self.end = self.idx;
+            None
+        }
     }
 }
+impl<I: Idx> ExactSizeIterator for RngItr<I> {}
+impl<I: Idx> FusedIterator for RngItr<I> {}
 
-#[cfg(test)]
-mod tst {
-    use super::*;
+/// Returns an iterator which pairs each segment of `0..lim` with the
+/// segment expanded by `lft` elements before and `rgt` elements after it.
+///
+/// Equivalent to `rngs(seg, lim).halo(lft, rgt)`. See [`RngItr::halo`].
+///
+/// # Panics
+///
+/// Panics if `seg` is zero.
+pub fn rngs_halo(seg: usize, lim: usize, lft: usize, rgt: usize) -> HaloItr {
+    rngs(seg, lim).halo(lft, rgt)
+}
 
-    #[test]
-    fn rngs_n() {
-        assert_eq!(rngs(2, 6).collect::<Vec<Range<usize>>>(), [0..3, 3..6]);
-        assert_eq!(
-            rngs(2, 7).collect::<Vec<Range<usize>>>(),
-            [0..4, 4..7]
-        );
-        assert_eq!(
-            rngs(4, 10).collect::<Vec<Range<usize>>>(),
-            [0..3, 3..6, 6..8, 8..10]
-        );
+/// A range iterator which yields each segment with its halo.
+///
+/// Items are `(core, ext)` pairs, where `ext` is `core` expanded by the
+/// halo widths.
+///
+/// With the `serde` feature, an iterator serializes as the schema of
+/// [`RngItr`] with the halo widths, `{ off, lim, seg, rem, idx, end, lft,
+/// rgt }`. `lim`, `lft` and `rgt` are `u128`s, as in the plan.
+#[derive(Debug, Clone)]
+pub struct HaloItr<I = usize> {
+    itr: RngItr<I>,
+    lft: u128,
+    rgt: u128,
+}
+impl<I: Idx> HaloItr<I> {
+    /// Returns `core` paired with its expanded range.
+    fn ext(&self, core: Range<I>) -> (Range<I>, Range<I>) {
+        let prt = &self.itr.prt;
+        let off = prt.off;
+        let lo = off.dst(core.start).saturating_sub(self.lft);
+        let hi = off
+            .dst(core.end)
+            .saturating_add(self.rgt)
+            .min(prt.bnd(prt.cnt));
+        (core, off.fwd(lo)..off.fwd(hi))
+    }
+}
+impl<I: Idx> Iterator for HaloItr<I> {
+    type Item = (Range<I>, Range<I>);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.itr.next().map(|core| self.ext(core))
+    }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.itr.nth(n).map(|core| self.ext(core))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.itr.size_hint()
+    }
+    fn count(self) -> usize {
+        self.len()
+    }
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+impl<I: Idx> DoubleEndedIterator for HaloItr<I> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.itr.next_back().map(|core| self.ext(core))
+    }
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.itr.nth_back(n).map(|core| self.ext(core))
+    }
+}
+impl<I: Idx> ExactSizeIterator for HaloItr<I> {}
+impl<I: Idx> FusedIterator for HaloItr<I> {}
+
+/// An inclusive range iterator.
+///
+/// With the `serde` feature, an iterator serializes as
+/// `{ lo, hi, seg, rem, idx, end }`, where `lo..=hi` is the whole range. An
+/// empty iterator has a zero `seg` and `hi` equal to `lo`.
+#[derive(Debug, Clone)]
+pub struct RngIncItr<I = usize> {
+    itr: RngItr<I>,
+}
+impl<I: Idx> RngIncItr<I> {
+    /// Returns the iterator with the remainder placed by `rem`.
+    ///
+    /// See [`Partition::rem_pol`].
+    pub fn rem_pol(self, rem: RemainderPolicy) -> Self {
+        RngIncItr {
+            itr: self.itr.rem_pol(rem),
+        }
+    }
+
+    /// Returns the inclusive range of segment `idx`.
+    fn rng(&self, idx: usize) -> RangeInclusive<I> {
+        let prt = &self.itr.prt;
+        let lo = prt.bnd(idx);
+        // Segments are never empty, so the last offset cannot overflow.
+        let lst = match prt.xtr(idx) {
+            0 => lo + (prt.stp - 1),
+            xtr => lo + prt.stp + (xtr - 1),
+        };
+        prt.off.fwd(lo)..=prt.off.fwd(lst)
+    }
+}
+impl<I: Idx> Iterator for RngIncItr<I> {
+    type Item = RangeInclusive<I>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.itr.idx == self.itr.end {
+            None
+        } else {
+            self.itr.idx += 1;
+            Some(self.rng(self.itr.idx - 1))
+        }
+    }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n < self.itr.end - self.itr.idx {
+            self.itr.idx += n;
+            self.next()
+        } else {
+            self.itr.idx = self.itr.end;
+            None
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.itr.size_hint()
+    }
+    fn count(self) -> usize {
+        self.len()
+    }
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+impl<I: Idx> DoubleEndedIterator for RngIncItr<I> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.itr.idx == self.itr.end {
+            None
+        } else {
+            self.itr.end -= 1;
+            Some(self.rng(self.itr.end))
+        }
+    }
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if n < self.itr.end - self.itr.idx {
+            self.itr.end -= n;
+            self.next_back()
+        } else {
+            self.itr.end = self.itr.idx;
+            None
+        }
+    }
+}
+impl<I: Idx> ExactSizeIterator for RngIncItr<I> {}
+impl<I: Idx> FusedIterator for RngIncItr<I> {}
+
+/// Returns a range iterator with segment lengths proportional to `wgts`.
+///
+/// ```text
+/// wgts=[4, 2, 1, 1], lim=16: [0..8, 8..12, 12..14, 14..16]
+/// wgts=[4, 2, 1, 1], lim=10: [0..5, 5..8, 8..9, 9..10]
+/// wgts=[1, 1, 1, 1], lim=10: [0..3, 3..6, 6..8, 8..10]
+/// wgts=[1, 0, 1],    lim=4:  [0..2, 2..2, 2..4]
+/// ```
+///
+/// Each segment receives `lim * wgt / sum` elements rounded down. The
+/// remaining elements go one each to the segments with the largest
+/// rounding remainders, earlier segments first on ties. With equal
+/// weights this matches [`rngs`], except that `lim` smaller than the
+/// segment count yields trailing empty segments.
+///
+/// # Arguments
+///
+/// * `wgts` - The relative weight of each segment.
+///
+/// * `lim` - The total number of elements.
+///
+/// # Panics
+///
+/// Panics if the weights sum to zero.
+pub fn rngs_weighted(wgts: &[usize], lim: usize) -> BndItr {
+    let sum: u128 = wgts.iter().map(|&wgt| wgt as u128).sum();
+    assert!(sum != 0, "weights must not sum to zero");
+
+    // Allocate the rounded down quotas.
+    let mut lens = Vec::with_capacity(wgts.len());
+    let mut rems = Vec::with_capacity(wgts.len());
+    for (idx, &wgt) in wgts.iter().enumerate() {
+        let qta = lim as u128 * wgt as u128;
+        lens.push((qta / sum) as usize);
+        rems.push((qta % sum, idx));
+    }
+
+    // Hand out the leftover elements by largest remainder.
+    let lft = lim - lens.iter().sum::<usize>();
+    rems.sort_unstable_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    for &(_, idx) in &rems[..lft] {
+        lens[idx] += 1;
+    }
+
+    let mut bnds = Vec::with_capacity(lens.len() + 1);
+    bnds.push(0);
+    for len in lens {
+        bnds.push(bnds[bnds.len() - 1] + len);
+    }
+    BndItr::new(bnds)
+}
+
+/// A range iterator over precomputed segment boundaries.
+///
+/// With the `serde` feature, an iterator serializes as `{ bnds }`, the
+/// bounds of its remaining segments.
+#[derive(Debug, Clone)]
+pub struct BndItr {
+    bnds: Vec<usize>,
+    idx: usize,
+    end: usize,
+}
+impl BndItr {
+    /// Returns an iterator over the segments between ascending `bnds`.
+    fn new(bnds: Vec<usize>) -> Self {
+        let end = bnds.len().saturating_sub(1);
+        BndItr { bnds, idx: 0, end }
+    }
+}
+impl Iterator for BndItr {
+    type Item = Range<usize>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx == self.end {
+            None
+        } else {
+            self.idx += 1;
+            Some(self.bnds[self.idx - 1]..self.bnds[self.idx])
+        }
+    }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n < self.end - self.idx {
+            self.idx += n;
+            self.next()
+        } else {
+            self.idx = self.end;
+            None
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.idx;
+        (len, Some(len))
+    }
+    fn count(self) -> usize {
+        self.len()
+    }
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+impl DoubleEndedIterator for BndItr {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx == self.end {
+            None
+        } else {
+            self.end -= 1;
+            Some(self.bnds[self.end]..self.bnds[self.end + 1])
+        }
+    }
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if n < self.end - self.idx {
+            self.end -= n;
+            self.next_back()
+        } else {
+            self.end = self.idx;
+            None
+        }
+    }
+}
+impl ExactSizeIterator for BndItr {}
+impl FusedIterator for BndItr {}
+
+/// Returns a range iterator which balances per-element costs.
+///
+/// ```text
+/// seg=2, cst=[1, 1, 1, 1]:    [0..2, 2..4]
+/// seg=2, cst=[9, 1, 1, 1]:    [0..1, 1..4]
+/// seg=3, cst=[1, 2, 3, 4, 5]: [0..3, 3..4, 4..5]
+/// ```
+///
+/// Segments are contiguous and minimize the largest segment cost. As with
+/// [`rngs`], the segment count is clamped to the number of elements, and
+/// no elements yields a single `0..0`.
+///
+/// # Arguments
+///
+/// * `seg` - The number of segments to divide the elements.
+///
+/// * `cst` - The cost of each element.
+///
+/// # Panics
+///
+/// Panics if `seg` is zero or the total cost overflows.
+pub fn rngs_cst(seg: usize, cst: &[u64]) -> BndItr {
+    let pfx: Vec<u64> = cst
+        .iter()
+        .scan(0u64, |sum, &cst| {
+            *sum = sum.checked_add(cst).expect("total cost overflows u64");
+            Some(*sum)
+        })
+        .collect();
+    rngs_cst_pfx(seg, &pfx)
+}
+
+/// Returns a range iterator which balances per-element costs given as
+/// prefix sums.
+///
+/// `pfx[i]` is the total cost of elements `0..=i`, as produced by an
+/// inclusive scan of the costs. See [`rngs_cst`].
+///
+/// # Arguments
+///
+/// * `seg` - The number of segments to divide the elements.
+///
+/// * `pfx` - The inclusive prefix sums of the element costs.
+///
+/// # Panics
+///
+/// Panics if `seg` is zero or `pfx` is decreasing.
+pub fn rngs_cst_pfx(seg: usize, pfx: &[u64]) -> BndItr {
+    assert!(seg != 0, "seg must be non-zero");
+    let lim = pfx.len();
+    let cnt = seg.min(lim).max(1);
+
+    // The largest segment cost lies between the largest element cost, or
+    // an even share of the total, and the total.
+    let mut lo = pfx.first().copied().unwrap_or(0);
+    for idx in 1..lim {
+        let cst = pfx[idx]
+            .checked_sub(pfx[idx - 1])
+            .expect("pfx must be non-decreasing");
+        lo = lo.max(cst);
+    }
+    let tot = pfx.last().copied().unwrap_or(0);
+    lo = lo.max(tot.div_ceil(cnt as u64));
+    let mut hi = tot;
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if cst_bnds(pfx, cnt, mid).is_some() {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    BndItr::new(cst_bnds(pfx, cnt, lo).expect("total cost fits a segment"))
+}
+
+/// Returns the boundaries of `cnt` segments costing at most `max` each,
+/// or `None` if there are none.
+///
+/// Each segment greedily takes as many elements as fit, leaving at least
+/// one element for every later segment.
+fn cst_bnds(pfx: &[u64], cnt: usize, max: u64) -> Option<Vec<usize>> {
+    let lim = pfx.len();
+    let mut bnds = Vec::with_capacity(cnt + 1);
+    bnds.push(0);
+    let mut beg = 0;
+    for idx in 1..cnt {
+        let bse = if beg == 0 { 0 } else { pfx[beg - 1] };
+        let cap = lim - (cnt - idx);
+        beg += pfx[beg..cap].partition_point(|&sum| sum - bse <= max);
+        bnds.push(beg);
+    }
+    let bse = if beg == 0 { 0 } else { pfx[beg - 1] };
+    if pfx.last().copied().unwrap_or(0) - bse > max {
+        return None;
+    }
+    bnds.push(lim);
+    Some(bnds)
+}
+
+/// Returns an iterator over the tiles of an N-dimensional grid, in
+/// row-major order.
+///
+/// ```text
+/// seg=[2, 2], lim=[4, 6]: [[0..2, 0..3], [0..2, 3..6], [2..4, 0..3], [2..4, 3..6]]
+/// ```
+///
+/// Each axis is divided as [`rngs`] divides it. See [`tiles_ord`].
+///
+/// # Arguments
+///
+/// * `seg` - The number of segments to divide each axis.
+///
+/// * `lim` - The number of elements along each axis.
+///
+/// # Panics
+///
+/// Panics if any `seg` is zero or the tile count overflows.
+pub fn tiles<const N: usize>(seg: &[usize; N], lim: &[usize; N]) -> TileItr<N> {
+    tiles_ord(seg, lim, TileOrd::RowMaj)
+}
+
+/// Returns an iterator over the tiles of an N-dimensional grid, in the
+/// given order.
+///
+/// ```text
+/// seg=[2, 2], lim=[4, 6], RowMaj: [[0..2, 0..3], [0..2, 3..6], [2..4, 0..3], [2..4, 3..6]]
+/// seg=[2, 2], lim=[4, 6], ColMaj: [[0..2, 0..3], [2..4, 0..3], [0..2, 3..6], [2..4, 3..6]]
+/// ```
+///
+/// # Arguments
+///
+/// * `seg` - The number of segments to divide each axis.
+///
+/// * `lim` - The number of elements along each axis.
+///
+/// * `ord` - The order in which tiles are visited.
+///
+/// # Panics
+///
+/// Panics if any `seg` is zero or the tile count overflows.
+pub fn tiles_ord<const N: usize>(seg: &[usize; N], lim: &[usize; N], ord: TileOrd) -> TileItr<N> {
+    let axs: [Partition; N] = std::array::from_fn(|ax| prt(seg[ax], lim[ax]));
+    let end = axs
+        .iter()
+        .try_fold(1usize, |cnt, ax| cnt.checked_mul(ax.len()))
+        .expect("tile count overflows usize");
+    TileItr {
+        axs,
+        ord,
+        idx: 0,
+        end,
+    }
+}
+
+/// Returns an iterator over `cnt` tiles of an N-dimensional grid, with
+/// tiles as close to square as `cnt` allows, in row-major order.
+///
+/// ```text
+/// cnt=4, lim=[100, 100]: seg=[2, 2]
+/// cnt=8, lim=[400, 100]: seg=[4, 2]
+/// cnt=8, lim=[10, 10, 10]: seg=[2, 2, 2]
+/// ```
+///
+/// The per-axis segment counts multiply to `cnt` and minimize the ratio of
+/// the longest to the shortest tile side, ignoring zero-length axes. Earlier
+/// axes receive fewer segments on ties. No axis receives more segments than
+/// elements, so a zero-length axis receives one.
+///
+/// ```text
+/// cnt=4, lim=[0, 100]: seg=[1, 4]
+/// cnt=8, lim=[2, 2]:   seg=[2, 2]
+/// cnt=5, lim=[2, 2]:   seg=[2, 2]
+/// ```
+///
+/// When no such factorization of `cnt` exists, the largest smaller count
+/// with one is used. The iterator's length is the resulting tile count.
+///
+/// # Arguments
+///
+/// * `cnt` - The total number of tiles.
+///
+/// * `lim` - The number of elements along each axis.
+///
+/// # Panics
+///
+/// Panics if `cnt` is zero or `N` is zero.
+pub fn tiles_sqr<const N: usize>(cnt: usize, lim: &[usize; N]) -> TileItr<N> {
+    assert!(cnt != 0, "cnt must be non-zero");
+    assert!(N != 0, "grid must have an axis");
+    let max = lim
+        .iter()
+        .try_fold(1usize, |max, &lim| max.checked_mul(lim.max(1)))
+        .unwrap_or(usize::MAX);
+    let mut seg = [1; N];
+    let mut bst = ([1; N], f64::INFINITY);
+    // A single tile always fits, so the search ends.
+    for cnt in (1..=cnt.min(max)).rev() {
+        sqr_segs(cnt, lim, &mut seg, 0, &mut bst.0, &mut bst.1);
+        if bst.1.is_finite() {
+            break;
+        }
+    }
+    tiles(&bst.0, lim)
+}
+
+/// Searches the factorizations of `cnt` over axes `ax..` with at most
+/// `lim.max(1)` segments per axis for the one with the squarest tiles,
+/// recording it in `bst` and `bst_scr`.
+fn sqr_segs(
+    cnt: usize,
+    lim: &[usize],
+    seg: &mut [usize],
+    ax: usize,
+    bst: &mut [usize],
+    bst_scr: &mut f64,
+) {
+    if ax == seg.len() - 1 {
+        if cnt > lim[ax].max(1) {
+            return;
+        }
+        seg[ax] = cnt;
+        let sds = lim
+            .iter()
+            .zip(seg.iter())
+            .filter(|(&lim, _)| lim != 0)
+            .map(|(&lim, &seg)| lim as f64 / seg as f64);
+        let (min, max) = sds.fold((f64::INFINITY, 0f64), |(min, max), sd| {
+            (min.min(sd), max.max(sd))
+        });
+        // Only zero-length axes yield empty tiles of equal shape.
+        let scr = if min.is_finite() { max / min } else { 1.0 };
+        if scr < *bst_scr {
+            *bst_scr = scr;
+            bst.copy_from_slice(seg);
+        }
+        return;
+    }
+    let mut divs: Vec<usize> = (1..)
+        .take_while(|&div| div <= cnt / div)
+        .filter(|&div| cnt % div == 0)
+        .flat_map(|div| [div, cnt / div])
+        .filter(|&div| div <= lim[ax].max(1))
+        .collect();
+    divs.sort_unstable();
+    divs.dedup();
+    for div in divs {
+        seg[ax] = div;
+        sqr_segs(cnt / div, lim, seg, ax + 1, bst, bst_scr);
+    }
+}
+
+/// The order in which [`TileItr`] visits tiles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TileOrd {
+    /// The last axis varies fastest, as in C arrays.
+    #[default]
+    RowMaj,
+    /// The first axis varies fastest, as in Fortran arrays.
+    ColMaj,
+}
+
+/// An iterator over the tiles of an N-dimensional grid.
+///
+/// With the `serde` feature, an iterator serializes as
+/// `{ seg, lim, ord, idx, end }`, with a `seg` and `lim` per axis.
+#[derive(Debug, Clone)]
+pub struct TileItr<const N: usize> {
+    axs: [Partition; N],
+    ord: TileOrd,
+    idx: usize,
+    end: usize,
+}
+impl<const N: usize> TileItr<N> {
+    /// Returns the tile at position `idx` in visiting order.
+    fn tile(&self, mut idx: usize) -> [Range<usize>; N] {
+        let mut segs = [0; N];
+        let mut split = |ax: usize| {
+            let len = self.axs[ax].len();
+            segs[ax] = idx % len;
+            idx /= len;
+        };
+        match self.ord {
+            TileOrd::RowMaj => (0..N).rev().for_each(&mut split),
+            TileOrd::ColMaj => (0..N).for_each(&mut split),
+        }
+        std::array::from_fn(|ax| self.axs[ax].rng(segs[ax]))
+    }
+}
+impl<const N: usize> Iterator for TileItr<N> {
+    type Item = [Range<usize>; N];
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx == self.end {
+            None
+        } else {
+            self.idx += 1;
+            Some(self.tile(self.idx - 1))
+        }
+    }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n < self.end - self.idx {
+            self.idx += n;
+            self.next()
+        } else {
+            self.idx = self.end;
+            None
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.idx;
+        (len, Some(len))
+    }
+    fn count(self) -> usize {
+        self.len()
+    }
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+impl<const N: usize> DoubleEndedIterator for TileItr<N> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx == self.end {
+            None
+        } else {
+            self.end -= 1;
+            Some(self.tile(self.end))
+        }
+    }
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if n < self.end - self.idx {
+            self.end -= n;
+            self.next_back()
+        } else {
+            self.end = self.idx;
+            None
+        }
+    }
+}
+impl<const N: usize> ExactSizeIterator for TileItr<N> {}
+impl<const N: usize> FusedIterator for TileItr<N> {}
+
+/// Returns a range iterator whose interior boundaries are multiples of
+/// `aln`.
+///
+/// ```text
+/// seg=2, lim=64, aln=16: [0..32, 32..64]
+/// seg=3, lim=64, aln=16: [0..32, 32..48, 48..64]
+/// seg=2, lim=50, aln=16: [0..32, 32..50]
+/// seg=4, lim=20, aln=16: [0..16, 16..20]
+/// ```
+///
+/// `0..lim` is divided into blocks of `aln` elements, the last of which
+/// may be partial, and the blocks are divided as [`rngs`] divides them.
+/// The segment count is reduced to the block count where needed. See
+/// [`AlnItr::imb`] for the resulting imbalance.
+///
+/// # Arguments
+///
+/// * `seg` - The number of segments to divide the length.
+///
+/// * `lim` - The total number of elements.
+///
+/// * `aln` - The alignment of boundaries, in elements.
+///
+/// # Panics
+///
+/// Panics if `seg` or `aln` is zero.
+pub fn rngs_aln(seg: usize, lim: usize, aln: usize) -> AlnItr {
+    assert!(aln != 0, "aln must be non-zero");
+    let itr = rngs(seg, lim.div_ceil(aln));
+    let mut aln_itr = AlnItr {
+        itr,
+        lim,
+        aln,
+        imb: 0,
+    };
+    // Leading segments take the extra blocks, and the last segment the
+    // partial block, so they are the longest and shortest.
+    if let (Some(fst), Some(lst)) = (aln_itr.clone().next(), aln_itr.clone().next_back()) {
+        aln_itr.imb = fst.len() - lst.len();
+    }
+    aln_itr
+}
+
+/// Returns a range iterator whose interior boundaries fall on multiples of
+/// `aln_byt` bytes, for elements of `elm_byt` bytes.
+///
+/// ```text
+/// seg=2, lim=32, aln_byt=64, elm_byt=4:  [0..16, 16..32]
+/// seg=2, lim=32, aln_byt=64, elm_byt=24: [0..16, 16..32]
+/// ```
+///
+/// Boundaries are aligned to the smallest element count whose size is a
+/// multiple of `aln_byt`, assuming the buffer itself is aligned. See
+/// [`rngs_aln`].
+///
+/// # Arguments
+///
+/// * `seg` - The number of segments to divide the length.
+///
+/// * `lim` - The total number of elements.
+///
+/// * `aln_byt` - The alignment of boundaries, in bytes.
+///
+/// * `elm_byt` - The size of an element, in bytes.
+///
+/// # Panics
+///
+/// Panics if `seg`, `aln_byt` or `elm_byt` is zero.
+pub fn rngs_aln_byt(seg: usize, lim: usize, aln_byt: usize, elm_byt: usize) -> AlnItr {
+    assert!(elm_byt != 0, "elm_byt must be non-zero");
+    rngs_aln(seg, lim, aln_byt / gcd(aln_byt, elm_byt))
+}
+
+/// Returns the greatest common divisor of `a` and `b`.
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// A range iterator with aligned boundaries.
+///
+/// With the `serde` feature, an iterator serializes as
+/// `{ seg, lim, aln, idx, end }`.
+#[derive(Debug, Clone)]
+pub struct AlnItr {
+    itr: RngItr,
+    lim: usize,
+    aln: usize,
+    imb: usize,
+}
+impl AlnItr {
+    /// Returns the length difference between the longest and shortest
+    /// segment.
+    ///
+    /// ```text
+    /// seg=3, lim=64, aln=16: 16
+    /// seg=2, lim=50, aln=16: 14
+    /// seg=4, lim=64, aln=16: 0
+    /// ```
+    pub fn imb(&self) -> usize {
+        self.imb
+    }
+
+    /// Returns the element range of the block range `blks`.
+    fn rng(&self, blks: Range<usize>) -> Range<usize> {
+        blks.start * self.aln..blks.end.saturating_mul(self.aln).min(self.lim)
+    }
+}
+impl Iterator for AlnItr {
+    type Item = Range<usize>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.itr.next().map(|blks| self.rng(blks))
+    }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.itr.nth(n).map(|blks| self.rng(blks))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.itr.size_hint()
+    }
+    fn count(self) -> usize {
+        self.len()
+    }
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+impl DoubleEndedIterator for AlnItr {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.itr.next_back().map(|blks| self.rng(blks))
+    }
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.itr.nth_back(n).map(|blks| self.rng(blks))
+    }
+}
+impl ExactSizeIterator for AlnItr {}
+impl FusedIterator for AlnItr {}
+
+/// Calls `f` on each segment of `0..lim`, one scoped thread per segment.
+///
+/// Segments follow [`rngs`]. Returns once every call has returned.
+///
+/// # Arguments
+///
+/// * `seg` - The number of segments to divide the length.
+///
+/// * `lim` - The total number of elements.
+///
+/// * `f` - The function called with each segment.
+///
+/// # Panics
+///
+/// Panics if `seg` is zero, or with the segment index if a call to `f`
+/// panics.
+pub fn par_for_each_rng<F>(seg: usize, lim: usize, f: F)
+where
+    F: Fn(Range<usize>) + Sync,
+{
+    par_map_rng(seg, lim, f);
+}
+
+/// Returns the results of calling `f` on each segment of `0..lim`, one
+/// scoped thread per segment.
+///
+/// ```text
+/// seg=4, lim=10, f=|rng| rng.len(): [3, 3, 2, 2]
+/// ```
+///
+/// Segments follow [`rngs`], and results are in segment order.
+///
+/// # Arguments
+///
+/// * `seg` - The number of segments to divide the length.
+///
+/// * `lim` - The total number of elements.
+///
+/// * `f` - The function called with each segment.
+///
+/// # Panics
+///
+/// Panics if `seg` is zero, or with the segment index if a call to `f`
+/// panics. The first panicking segment in order is reported, after all
+/// threads finish.
+pub fn par_map_rng<R, F>(seg: usize, lim: usize, f: F) -> Vec<R>
+where
+    R: Send,
+    F: Fn(Range<usize>) -> R + Sync,
+{
+    let f = &f;
+    thread::scope(|scp| {
+        let hnds: Vec<_> = rngs(seg, lim)
+            .map(|rng| scp.spawn(move || f(rng)))
+            .collect();
+        hnds.into_iter()
+            .enumerate()
+            .map(|(idx, hnd)| match hnd.join() {
+                Ok(ret) => ret,
+                Err(pld) => panic!("rngs segment {idx} panicked: {}", pnc_msg(&*pld)),
+            })
+            .collect()
+    })
+}
+
+/// Returns the message of a panic payload.
+fn pnc_msg(pld: &(dyn Any + Send)) -> &str {
+    if let Some(msg) = pld.downcast_ref::<&str>() {
+        msg
+    } else if let Some(msg) = pld.downcast_ref::<String>() {
+        msg
+    } else {
+        "Box<dyn Any>"
+    }
+}
+
+/// Returns an iterator which splits `sl` into disjoint mutable sub-slices
+/// at the given ranges.
+///
+/// ```text
+/// sl=[a, b, c, d, e], rngs=rngs(2, 5): [(0..3, [a, b, c]), (3..5, [d, e])]
+/// ```
+///
+/// Works with any partition yielding `Range<usize>`, such as [`rngs`],
+/// [`rngs_weighted`] or [`rngs_aln`]. Ranges may leave gaps between them.
+///
+/// # Arguments
+///
+/// * `sl` - The slice to split.
+///
+/// * `rngs` - The ascending, disjoint ranges of the sub-slices.
+///
+/// # Panics
+///
+/// Panics during iteration if a range overlaps or precedes an earlier
+/// range, or extends past the end of `sl`.
+pub fn split_mut_by<T, R>(sl: &mut [T], rngs: R) -> SplitMutItr<'_, T, R::IntoIter>
+where
+    R: IntoIterator<Item = Range<usize>>,
+{
+    SplitMutItr {
+        sl,
+        off: 0,
+        rngs: rngs.into_iter(),
+    }
+}
+
+/// Splits slices into disjoint mutable sub-slices at the given ranges.
+pub trait SplitMutBy<T> {
+    /// Returns an iterator which splits `self` into disjoint mutable
+    /// sub-slices at the given ranges. See [`split_mut_by`].
+    fn split_mut_by<R>(&mut self, rngs: R) -> SplitMutItr<'_, T, R::IntoIter>
+    where
+        R: IntoIterator<Item = Range<usize>>;
+}
+impl<T> SplitMutBy<T> for [T] {
+    fn split_mut_by<R>(&mut self, rngs: R) -> SplitMutItr<'_, T, R::IntoIter>
+    where
+        R: IntoIterator<Item = Range<usize>>,
+    {
+        split_mut_by(self, rngs)
+    }
+}
+
+/// An iterator over disjoint mutable sub-slices.
+#[derive(Debug)]
+pub struct SplitMutItr<'a, T, R> {
+    sl: &'a mut [T],
+    off: usize,
+    rngs: R,
+}
+impl<'a, T, R> Iterator for SplitMutItr<'a, T, R>
+where
+    R: Iterator<Item = Range<usize>>,
+{
+    type Item = (Range<usize>, &'a mut [T]);
+    fn next(&mut self) -> Option<Self::Item> {
+        let rng = self.rngs.next()?;
+        assert!(
+            self.off <= rng.start && rng.start <= rng.end,
+            "range {rng:?} is not ascending and disjoint from ranges ending at {}",
+            self.off
+        );
+        assert!(
+            rng.end - self.off <= self.sl.len(),
+            "range {rng:?} is out of bounds for slice of length {}",
+            self.off + self.sl.len()
+        );
+        let sl = mem::take(&mut self.sl);
+        let (_, sl) = sl.split_at_mut(rng.start - self.off);
+        let (hd, tl) = sl.split_at_mut(rng.end - rng.start);
+        self.sl = tl;
+        self.off = rng.end;
+        Some((rng, hd))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rngs.size_hint()
+    }
+}
+impl<T, R> ExactSizeIterator for SplitMutItr<'_, T, R> where
+    R: ExactSizeIterator<Item = Range<usize>>
+{
+}
+
+/// A thread-safe dispenser of range chunks over `0..lim`.
+///
+/// Chunks are taken from an atomic cursor, so a dispenser shared by
+/// reference lets workers take work as they finish. Chunk lengths depend
+/// only on the cursor position, so the sequence of chunks is
+/// deterministic regardless of which worker takes them.
+///
+/// ```text
+/// lim=10,  wkrs=2, Fixed(4):     [0..4, 4..8, 8..10]
+/// lim=100, wkrs=4, Guided(10):   [0..25, 25..44, 44..58, 58..69, 69..79, 79..89, 89..99, 99..100]
+/// lim=100, wkrs=2, Factoring(8): [0..25, 25..50, 50..63, 63..76, 76..84, 84..92, 92..100]
+/// ```
+#[derive(Debug)]
+pub struct ChunkDispenser {
+    cur: AtomicUsize,
+    lim: usize,
+    wkrs: usize,
+    sch: ChunkSchedule,
+}
+impl ChunkDispenser {
+    /// Returns a dispenser of chunks over `0..lim`.
+    ///
+    /// # Arguments
+    ///
+    /// * `lim` - The total number of elements.
+    ///
+    /// * `wkrs` - The number of workers sharing the dispenser.
+    ///
+    /// * `sch` - How chunk lengths are chosen.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `wkrs` or a fixed chunk length is zero.
+    pub fn new(lim: usize, wkrs: usize, sch: ChunkSchedule) -> Self {
+        assert!(wkrs != 0, "wkrs must be non-zero");
+        assert!(
+            sch != ChunkSchedule::Fixed(0),
+            "chunk length must be non-zero"
+        );
+        ChunkDispenser {
+            cur: AtomicUsize::new(0),
+            lim,
+            wkrs,
+            sch,
+        }
+    }
+
+    /// Returns the next chunk, or `None` when all elements are dispensed.
+    pub fn take_chunk(&self) -> Option<Range<usize>> {
+        self.cur
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |cur| {
+                (cur < self.lim).then(|| cur + self.len_at(cur))
+            })
+            .ok()
+            .map(|cur| cur..cur + self.len_at(cur))
+    }
+
+    /// Returns the number of elements not yet dispensed.
+    pub fn rem(&self) -> usize {
+        self.lim - self.cur.load(Ordering::Relaxed)
+    }
+
+    /// Returns the length of the chunk starting at `cur`.
+    fn len_at(&self, cur: usize) -> usize {
+        let rem = self.lim - cur;
+        let len = match self.sch {
+            ChunkSchedule::Fixed(len) => len,
+            ChunkSchedule::Guided(min) => rem.div_ceil(self.wkrs).max(min),
+            ChunkSchedule::Factoring(min) => {
+                // Find the batch holding `cur`; each batch takes at least
+                // half of what remains, so there are few to skip.
+                let (mut pos, mut rem) = (0, self.lim);
+                loop {
+                    let len = rem.div_ceil(self.wkrs.saturating_mul(2)).max(min);
+                    let spn = len.saturating_mul(self.wkrs).min(rem);
+                    if cur < pos + spn {
+                        break len;
+                    }
+                    pos += spn;
+                    rem -= spn;
+                }
+            }
+        };
+        len.clamp(1, rem)
+    }
+}
+impl Iterator for &ChunkDispenser {
+    type Item = Range<usize>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.take_chunk()
+    }
+}
+impl FusedIterator for &ChunkDispenser {}
+
+/// How a [`ChunkDispenser`] chooses chunk lengths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChunkSchedule {
+    /// Chunks of a fixed length.
+    Fixed(usize),
+    /// Chunks of the remaining length divided by the worker count, and at
+    /// least the given length, as OpenMP's `guided` schedule.
+    Guided(usize),
+    /// Batches of one chunk per worker, each batch covering half the
+    /// remaining length, with chunks at least the given length.
+    Factoring(usize),
+}
+
+/// Returns a tree which recursively halves `0..lim` down to leaves of at
+/// most `leaf` elements.
+///
+/// Equivalent to `rng_tree(2, leaf, lim)`. See [`rng_tree`].
+///
+/// # Panics
+///
+/// Panics if `leaf` is zero.
+pub fn bisect(leaf: usize, lim: usize) -> RngTree {
+    rng_tree(2, leaf, lim)
+}
+
+/// Returns a tree which recursively divides `0..lim` into `ary` children
+/// down to leaves of at most `leaf` elements.
+///
+/// ```text
+/// ary=2, leaf=2, lim=7:
+///   0..7
+///   ├── 0..4
+///   │   ├── 0..2
+///   │   └── 2..4
+///   └── 4..7
+///       ├── 4..6
+///       └── 6..7
+/// ```
+///
+/// Children divide their parent as [`rngs`] divides it. Nodes are ranges,
+/// and the tree is computed on demand rather than stored.
+///
+/// # Arguments
+///
+/// * `ary` - The number of children of each interior node.
+///
+/// * `leaf` - The maximum number of elements in a leaf. Leaves may hold
+///   fewer.
+///
+/// * `lim` - The total number of elements.
+///
+/// # Panics
+///
+/// Panics if `ary` is less than two or `leaf` is zero.
+pub fn rng_tree(ary: usize, leaf: usize, lim: usize) -> RngTree {
+    assert!(ary >= 2, "ary must be at least two");
+    assert!(leaf != 0, "leaf must be non-zero");
+    RngTree { ary, leaf, lim }
+}
+
+/// A tree of recursively divided ranges.
+///
+/// With the `serde` feature, a tree serializes as `{ ary, leaf, lim }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RngTree {
+    ary: usize,
+    leaf: usize,
+    lim: usize,
+}
+impl RngTree {
+    /// Returns the range of the root, `0..lim`.
+    pub fn root(&self) -> Range<usize> {
+        0..self.lim
+    }
+
+    /// Returns true if `rng` is not divided further.
+    pub fn is_leaf(&self, rng: &Range<usize>) -> bool {
+        rng.len() <= self.leaf
+    }
+
+    /// Returns the children of `rng`, or `None` if `rng` is a leaf.
+    pub fn children(&self, rng: &Range<usize>) -> Option<RngItr> {
+        (!self.is_leaf(rng)).then(|| rngs_rng(self.ary, rng.clone()))
+    }
+
+    /// Returns the parent of node `rng`, or `None` if `rng` is the root or
+    /// not a node.
+    pub fn parent(&self, rng: &Range<usize>) -> Option<Range<usize>> {
+        let mut cur = self.root();
+        while let Some(mut chds) = self.children(&cur) {
+            let chd = chds
+                .prt()
+                .segment_of(rng.start)
+                .and_then(|idx| chds.nth(idx))?;
+            if chd == *rng {
+                return Some(cur);
+            }
+            cur = chd;
+        }
+        None
+    }
+
+    /// Returns the depth of node `rng`, or `None` if `rng` is not a node.
+    ///
+    /// The root has depth zero.
+    pub fn depth_of(&self, rng: &Range<usize>) -> Option<usize> {
+        let (mut cur, mut dpt) = (self.root(), 0);
+        loop {
+            if cur == *rng {
+                return Some(dpt);
+            }
+            let mut chds = self.children(&cur)?;
+            cur = chds
+                .prt()
+                .segment_of(rng.start)
+                .and_then(|idx| chds.nth(idx))?;
+            dpt += 1;
+        }
+    }
+
+    /// Returns the depth of the deepest leaf.
+    ///
+    /// The first child of each node is the longest, so the deepest leaf
+    /// lies on the leftmost path.
+    pub fn depth(&self) -> usize {
+        let (mut cur, mut dpt) = (self.root(), 0);
+        while let Some(mut chds) = self.children(&cur) {
+            cur = chds.next().expect("interior nodes have children");
+            dpt += 1;
+        }
+        dpt
+    }
+
+    /// Returns an iterator over the leaves in the given order.
+    ///
+    /// ```text
+    /// ary=2, leaf=2, lim=5, Dfs: [0..2, 2..3, 3..5]
+    /// ary=2, leaf=2, lim=5, Bfs: [3..5, 0..2, 2..3]
+    /// ```
+    pub fn leaves(&self, ord: TreeOrd) -> LeafItr {
+        LeafItr {
+            tree: *self,
+            ord,
+            nds: VecDeque::from([self.root()]),
+        }
+    }
+}
+
+/// The order in which [`LeafItr`] visits leaves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TreeOrd {
+    /// Depth first, which visits leaves left to right.
+    #[default]
+    Dfs,
+    /// Breadth first, which visits shallower leaves first.
+    Bfs,
+}
+
+/// An iterator over the leaves of a [`RngTree`].
+#[derive(Debug, Clone)]
+pub struct LeafItr {
+    tree: RngTree,
+    ord: TreeOrd,
+    nds: VecDeque<Range<usize>>,
+}
+impl Iterator for LeafItr {
+    type Item = Range<usize>;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let nd = self.nds.pop_front()?;
+            match self.tree.children(&nd) {
+                None => return Some(nd),
+                Some(chds) => match self.ord {
+                    TreeOrd::Dfs => chds.rev().for_each(|chd| self.nds.push_front(chd)),
+                    TreeOrd::Bfs => self.nds.extend(chds),
+                },
+            }
+        }
+    }
+}
+impl FusedIterator for LeafItr {}
+
+/// Returns a range iterator over `buf` whose boundaries fall just after a
+/// `dlm` byte, so no record is split.
+///
+/// ```text
+/// seg=2, buf=b"ab\ncd\nef\n", dlm=b'\n': [0..6, 6..9]
+/// seg=3, buf=b"abcdefgh\ni\n", dlm=b'\n': [0..9, 9..11]
+/// seg=2, buf=b"ab\ncd", dlm=b'\n':       [0..3, 3..5]
+/// ```
+///
+/// Boundaries from [`rngs`] are moved forward to the next record start.
+/// Segments emptied by records longer than a segment are skipped, so
+/// fewer than `seg` ranges may be yielded, and an empty `buf` yields none.
+/// A missing trailing delimiter leaves the last record in the last range.
+///
+/// # Arguments
+///
+/// * `seg` - The number of segments to divide the buffer.
+///
+/// * `buf` - The bytes to divide.
+///
+/// * `dlm` - The byte ending each record.
+///
+/// # Panics
+///
+/// Panics if `seg` is zero.
+pub fn rngs_dlm(seg: usize, buf: &[u8], dlm: u8) -> BndItr {
+    rngs_dlm_by(seg, buf, |byt| byt == dlm)
+}
+
+/// Returns a range iterator over `buf` whose boundaries fall just after a
+/// byte matching `f`. See [`rngs_dlm`].
+///
+/// # Arguments
+///
+/// * `seg` - The number of segments to divide the buffer.
+///
+/// * `buf` - The bytes to divide.
+///
+/// * `f` - Returns true for bytes ending a record.
+///
+/// # Panics
+///
+/// Panics if `seg` is zero.
+pub fn rngs_dlm_by<F>(seg: usize, buf: &[u8], f: F) -> BndItr
+where
+    F: Fn(u8) -> bool,
+{
+    BndItr::new(fwd_bnds(seg, buf.len(), |bnd| {
+        if f(buf[bnd - 1]) {
+            bnd
+        } else {
+            buf[bnd..]
+                .iter()
+                .position(|&byt| f(byt))
+                .map_or(buf.len(), |pos| bnd + pos + 1)
+        }
+    }))
+}
+
+/// Returns the boundaries of [`rngs`] over `0..lim`, with each interior
+/// boundary moved forward by `fwd` and empty segments removed.
+///
+/// `fwd` receives a boundary in `1..lim` and returns a boundary no
+/// smaller, or `lim`.
+fn fwd_bnds<F>(seg: usize, lim: usize, mut fwd: F) -> Vec<usize>
+where
+    F: FnMut(usize) -> usize,
+{
+    let mut bnds = vec![0];
+    for rng in rngs(seg, lim).skip(1) {
+        let lst = bnds[bnds.len() - 1];
+        let bnd = fwd(rng.start.max(lst));
+        if bnd > lst && bnd < lim {
+            bnds.push(bnd);
+        }
+    }
+    if lim != 0 {
+        bnds.push(lim);
+    }
+    bnds
+}
+
+/// Returns a range iterator over the bytes of `rdr` whose boundaries fall
+/// just after a `dlm` byte. See [`rngs_dlm`].
+///
+/// Only the bytes following each tentative boundary are read, so large
+/// files are divided without reading them whole. `rdr` is left at an
+/// unspecified position.
+///
+/// # Arguments
+///
+/// * `seg` - The number of segments to divide the bytes.
+///
+/// * `rdr` - The source of the bytes, such as a file.
+///
+/// * `dlm` - The byte ending each record.
+///
+/// # Errors
+///
+/// Returns an error if seeking or reading `rdr` fails.
+///
+/// # Panics
+///
+/// Panics if `seg` is zero.
+pub fn rngs_dlm_rdr<R>(seg: usize, rdr: &mut R, dlm: u8) -> io::Result<BndItr>
+where
+    R: Read + Seek,
+{
+    rngs_dlm_rdr_by(seg, rdr, |byt| byt == dlm)
+}
+
+/// Returns a range iterator over the bytes of `rdr` whose boundaries fall
+/// just after a byte matching `f`. See [`rngs_dlm_rdr`].
+///
+/// # Errors
+///
+/// Returns an error if seeking or reading `rdr` fails. Interrupted reads
+/// are retried.
+///
+/// # Panics
+///
+/// Panics if `seg` is zero.
+pub fn rngs_dlm_rdr_by<R, F>(seg: usize, rdr: &mut R, f: F) -> io::Result<BndItr>
+where
+    R: Read + Seek,
+    F: Fn(u8) -> bool,
+{
+    let len = usize::try_from(rdr.seek(SeekFrom::End(0))?)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+    let mut bnds = vec![0];
+    let mut buf = vec![0; 8192];
+    for rng in rngs(seg, len).skip(1) {
+        let lst = bnds[bnds.len() - 1];
+        // Scan from the byte before the boundary for a record end.
+        let mut pos = rng.start.max(lst) - 1;
+        let mut bnd = len;
+        rdr.seek(SeekFrom::Start(pos as u64))?;
+        'scn: loop {
+            // Retry interrupted reads, as `Read::read_exact` does.
+            let cnt = match rdr.read(&mut buf) {
+                Ok(cnt) => cnt,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            if cnt == 0 {
+                break;
+            }
+            for &byt in &buf[..cnt] {
+                pos += 1;
+                if f(byt) {
+                    bnd = pos;
+                    break 'scn;
+                }
+            }
+        }
+        if bnd > lst && bnd < len {
+            bnds.push(bnd);
+        }
+    }
+    if len != 0 {
+        bnds.push(len);
+    }
+    Ok(BndItr::new(bnds))
+}
+
+/// Returns whether `chr` is a combining mark, general category Mn or Me.
+///
+/// Covers the common combining blocks rather than the full Unicode
+/// tables, so rarer marks, such as most Indic vowel signs, are missed.
+fn is_mrk(chr: char) -> bool {
+    matches!(chr,
+        '\u{300}'..='\u{36f}'
+        | '\u{483}'..='\u{489}'
+        | '\u{591}'..='\u{5bd}'
+        | '\u{5bf}'
+        | '\u{5c1}'..='\u{5c2}'
+        | '\u{5c4}'..='\u{5c5}'
+        | '\u{5c7}'
+        | '\u{610}'..='\u{61a}'
+        | '\u{64b}'..='\u{65f}'
+        | '\u{670}'
+        | '\u{6d6}'..='\u{6dc}'
+        | '\u{6df}'..='\u{6e4}'
+        | '\u{6e7}'..='\u{6e8}'
+        | '\u{6ea}'..='\u{6ed}'
+        | '\u{e31}'
+        | '\u{e34}'..='\u{e3a}'
+        | '\u{e47}'..='\u{e4e}'
+        | '\u{1ab0}'..='\u{1aff}'
+        | '\u{1dc0}'..='\u{1dff}'
+        | '\u{20d0}'..='\u{20f0}'
+        | '\u{302a}'..='\u{302d}'
+        | '\u{3099}'..='\u{309a}'
+        | '\u{fe00}'..='\u{fe0f}'
+        | '\u{fe20}'..='\u{fe2f}'
+        | '\u{e0100}'..='\u{e01ef}'
+    )
+}
+
+/// Returns a range iterator over the bytes of `s` whose boundaries fall
+/// on `bnd` boundaries, so every range slices `s` without panicking.
+///
+/// ```text
+/// seg=3, s="€€€€",       Char: [0..6, 6..9, 9..12]
+/// seg=2, s="ab cd ef",   Ws:   [0..6, 6..8]
+/// seg=3, s="ab, cd, ef", Word: [0..4, 4..8, 8..10]
+/// ```
+///
+/// Boundaries from [`rngs`] over `s.len()` are moved forward to the next
+/// `bnd` boundary. No boundary falls before a combining mark, so marks
+/// stay with the char they modify in every mode. Segments emptied by
+/// long words are skipped, so fewer than `seg` ranges may be yielded, and
+/// an empty `s` yields none.
+///
+/// # Arguments
+///
+/// * `seg` - The number of segments to divide the string.
+///
+/// * `s` - The string to divide.
+///
+/// * `bnd` - Where boundaries may fall.
+///
+/// # Panics
+///
+/// Panics if `seg` is zero.
+pub fn rngs_str(seg: usize, s: &str, bnd: StrBnd) -> BndItr {
+    BndItr::new(fwd_bnds(seg, s.len(), |min| {
+        // Start from the char containing `min`, paired with the last
+        // non-mark char before it. Marks never start a piece and are
+        // skipped when comparing neighbours.
+        let pos = (0..=min.min(s.len()))
+            .rev()
+            .find(|&pos| s.is_char_boundary(pos))
+            .unwrap_or(0);
+        let mut prv = s[..pos].chars().rev().find(|&chr| !is_mrk(chr));
+        for (idx, chr) in s[pos..].char_indices() {
+            if is_mrk(chr) {
+                continue;
+            }
+            let is_bnd = match (bnd, prv) {
+                (StrBnd::Char, _) => true,
+                (StrBnd::Ws, Some(prv)) => prv.is_whitespace() && !chr.is_whitespace(),
+                (StrBnd::Word, Some(prv)) => prv.is_alphanumeric() != chr.is_alphanumeric(),
+                (_, None) => false,
+            };
+            if is_bnd && pos + idx >= min {
+                return pos + idx;
+            }
+            prv = Some(chr);
+        }
+        s.len()
+    }))
+}
+
+/// Returns an iterator over pieces of `s` split on `bnd` boundaries.
+///
+/// ```text
+/// seg=3, s="€€€€", Char: ["€€", "€", "€"]
+/// seg=2, s="ab cd ef", Ws: ["ab cd ", "ef"]
+/// ```
+///
+/// See [`rngs_str`].
+///
+/// # Panics
+///
+/// Panics if `seg` is zero.
+pub fn strs(seg: usize, s: &str, bnd: StrBnd) -> StrItr<'_> {
+    StrItr {
+        s,
+        seg,
+        bnd,
+        itr: rngs_str(seg, s, bnd),
+    }
+}
+
+/// Where [`rngs_str`] boundaries may fall.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StrBnd {
+    /// Any char boundary.
+    ///
+    /// Combining marks are kept with the char they modify, but other
+    /// grapheme clusters, such as emoji joined by U+200D, may be split.
+    #[default]
+    Char,
+    /// The start of a non-whitespace char following whitespace, so words
+    /// keep their trailing whitespace.
+    ///
+    /// Combining marks are kept with the char they modify, and are
+    /// ignored when deciding whether a char follows whitespace.
+    Ws,
+    /// A change between alphanumeric and other chars.
+    ///
+    /// Combining marks are kept with the char they modify, and are
+    /// ignored when comparing neighbouring chars.
+    Word,
+}
+
+/// An iterator over pieces of a string.
+///
+/// The `serde` feature does not cover this iterator, as it borrows its
+/// string; serialize its [`Cursor`] instead.
+#[derive(Debug, Clone)]
+pub struct StrItr<'a> {
+    s: &'a str,
+    seg: usize,
+    bnd: StrBnd,
+    itr: BndItr,
+}
+impl<'a> Iterator for StrItr<'a> {
+    type Item = &'a str;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.itr.next().map(|rng| &self.s[rng])
+    }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.itr.nth(n).map(|rng| &self.s[rng])
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.itr.size_hint()
+    }
+    fn count(self) -> usize {
+        self.len()
+    }
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+impl DoubleEndedIterator for StrItr<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.itr.next_back().map(|rng| &self.s[rng])
+    }
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.itr.nth_back(n).map(|rng| &self.s[rng])
+    }
+}
+impl ExactSizeIterator for StrItr<'_> {}
+impl FusedIterator for StrItr<'_> {}
+
+/// Returns a cyclic distribution of `0..lim` over `seg` workers.
+///
+/// ```text
+/// seg=3, lim=8: wkr 0: [0, 3, 6], wkr 1: [1, 4, 7], wkr 2: [2, 5]
+/// ```
+///
+/// Equivalent to `blk_cyc(seg, 1, lim)`. See [`blk_cyc`].
+///
+/// # Panics
+///
+/// Panics if `seg` is zero.
+pub fn cyc(seg: usize, lim: usize) -> BlkCyc {
+    blk_cyc(seg, 1, lim)
+}
+
+/// Returns a block-cyclic distribution of `0..lim` over `seg` workers.
+///
+/// ```text
+/// seg=2, blk=3, lim=10: wkr 0: [0..3, 6..9], wkr 1: [3..6, 9..10]
+/// ```
+///
+/// `0..lim` is divided into blocks of `blk` elements, the last of which
+/// may be partial, and block `idx` is dealt to worker `idx % seg`.
+///
+/// # Arguments
+///
+/// * `seg` - The number of workers.
+///
+/// * `blk` - The number of elements in a block.
+///
+/// * `lim` - The total number of elements.
+///
+/// # Panics
+///
+/// Panics if `seg` or `blk` is zero.
+pub fn blk_cyc(seg: usize, blk: usize, lim: usize) -> BlkCyc {
+    assert!(seg != 0, "seg must be non-zero");
+    assert!(blk != 0, "blk must be non-zero");
+    BlkCyc { seg, blk, lim }
+}
+
+/// A block-cyclic distribution of a range over workers.
+///
+/// With the `serde` feature, a distribution serializes as
+/// `{ seg, blk, lim }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlkCyc {
+    seg: usize,
+    blk: usize,
+    lim: usize,
+}
+impl BlkCyc {
+    /// Returns the number of workers.
+    pub fn wkrs(&self) -> usize {
+        self.seg
+    }
+
+    /// Returns the worker owning `idx`, or `None` if `idx` is not below
+    /// `lim`.
+    pub fn owner(&self, idx: usize) -> Option<usize> {
+        (idx < self.lim).then(|| idx / self.blk % self.seg)
+    }
+
+    /// Returns an iterator over the blocks owned by worker `wkr`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `wkr` is not below the number of workers.
+    pub fn rngs(&self, wkr: usize) -> BlkCycItr {
+        assert!(wkr < self.seg, "wkr must be below the worker count");
+        let blks = self.lim.div_ceil(self.blk);
+        let end = if wkr < blks {
+            (blks - wkr - 1) / self.seg + 1
+        } else {
+            0
+        };
+        BlkCycItr {
+            dst: *self,
+            wkr,
+            idx: 0,
+            end,
+        }
+    }
+
+    /// Returns an iterator over the indexes owned by worker `wkr`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `wkr` is not below the number of workers.
+    pub fn idxs(&self, wkr: usize) -> Flatten<BlkCycItr> {
+        self.rngs(wkr).flatten()
+    }
+}
+
+/// An iterator over the blocks a worker owns in a block-cyclic
+/// distribution.
+#[derive(Debug, Clone)]
+pub struct BlkCycItr {
+    dst: BlkCyc,
+    wkr: usize,
+    idx: usize,
+    end: usize,
+}
+impl BlkCycItr {
+    /// Returns the range of the worker's block `idx`.
+    fn rng(&self, idx: usize) -> Range<usize> {
+        let beg = (self.wkr + idx * self.dst.seg) * self.dst.blk;
+        beg..beg.saturating_add(self.dst.blk).min(self.dst.lim)
+    }
+}
+impl Iterator for BlkCycItr {
+    type Item = Range<usize>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.idx == self.end {
+            None
+        } else {
+            self.idx += 1;
+            Some(self.rng(self.idx - 1))
+        }
+    }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n < self.end - self.idx {
+            self.idx += n;
+            self.next()
+        } else {
+            self.idx = self.end;
+            None
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.idx;
+        (len, Some(len))
+    }
+    fn count(self) -> usize {
+        self.len()
+    }
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+impl DoubleEndedIterator for BlkCycItr {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.idx == self.end {
+            None
+        } else {
+            self.end -= 1;
+            Some(self.rng(self.end))
+        }
+    }
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if n < self.end - self.idx {
+            self.end -= n;
+            self.next_back()
+        } else {
+            self.end = self.idx;
+            None
+        }
+    }
+}
+impl ExactSizeIterator for BlkCycItr {}
+impl FusedIterator for BlkCycItr {}
+
+/// Returns the range assigned to `rank` of `size` ranks, or `None` if
+/// `rank` has no segment.
+///
+/// ```text
+/// rank=1, size=3, lim=10: Some(4..7)
+/// rank=3, size=4, lim=3:  None
+/// ```
+///
+/// Equivalent to `rngs(size, lim).nth(rank)`, computed in constant time.
+///
+/// # Panics
+///
+/// Panics if `size` is zero.
+pub fn rng_for_rank(rank: usize, size: usize, lim: usize) -> Option<Range<usize>> {
+    prt(size, lim).get(rank)
+}
+
+/// Returns the range assigned to a rank path over nested rank groups, or
+/// `None` if a rank has no segment.
+///
+/// ```text
+/// ranks=[1, 0], sizes=[2, 3], lim=10: Some(5..7)
+/// ranks=[1, 2], sizes=[2, 3], lim=10: Some(9..10)
+/// ```
+///
+/// `ranks[0]` of `sizes[0]` selects a range of `0..lim` as with
+/// [`rng_for_rank`], such as a node's share; each later rank then selects
+/// a range within the previous one, such as a thread's share of its node.
+///
+/// # Panics
+///
+/// Panics if a size is zero.
+pub fn rng_for_ranks<const N: usize>(
+    ranks: &[usize; N],
+    sizes: &[usize; N],
+    lim: usize,
+) -> Option<Range<usize>> {
+    let mut rng = 0..lim;
+    for (&rank, &size) in ranks.iter().zip(sizes) {
+        let sub = rng_for_rank(rank, size, rng.len())?;
+        rng = rng.start + sub.start..rng.start + sub.end;
+    }
+    Some(rng)
+}
+
+/// Returns the range assigned to the rank read from the environment
+/// variable `var`.
+///
+/// ```text
+/// RANK=1, size=3, lim=10: Ok(4..7)
+/// RANK=x, size=3, lim=10: Err(Parse(..))
+/// ```
+///
+/// Equivalent to [`rng_for_rank`] with the rank parsed from `var`, such as
+/// `JOB_COMPLETION_INDEX` or `OMPI_COMM_WORLD_RANK`.
+///
+/// # Panics
+///
+/// Panics if `size` is zero.
+pub fn rng_for_rank_env(var: &str, size: usize, lim: usize) -> Result<Range<usize>, RankError> {
+    rng_for_rank_val(env::var(var), size, lim)
+}
+
+/// Returns the range assigned to the rank in the variable value `val`.
+fn rng_for_rank_val(
+    val: Result<String, env::VarError>,
+    size: usize,
+    lim: usize,
+) -> Result<Range<usize>, RankError> {
+    let val = val.map_err(RankError::Var)?;
+    let rank = val.trim().parse().map_err(RankError::Parse)?;
+    rng_for_rank(rank, size, lim).ok_or(RankError::Rank { rank, size })
+}
+
+/// An error returned when a rank cannot be read from the environment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RankError {
+    /// The variable is unset or not unicode.
+    Var(env::VarError),
+    /// The variable is not an unsigned integer.
+    Parse(ParseIntError),
+    /// The rank has no segment.
+    Rank { rank: usize, size: usize },
+}
+impl fmt::Display for RankError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RankError::Var(err) => write!(f, "rank variable unavailable: {err}"),
+            RankError::Parse(err) => write!(f, "rank variable invalid: {err}"),
+            RankError::Rank { rank, size } => {
+                write!(f, "rank {rank} of size {size} has no segment")
+            }
+        }
+    }
+}
+impl Error for RankError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RankError::Var(err) => Some(err),
+            RankError::Parse(err) => Some(err),
+            RankError::Rank { .. } => None,
+        }
+    }
+}
+
+/// Returns an iterator over the leaf ranges of a nested partition, with
+/// their paths of segment indexes.
+///
+/// ```text
+/// seg=[2, 2], lim=10: [([0, 0], 0..3), ([0, 1], 3..5), ([1, 0], 5..8), ([1, 1], 8..10)]
+/// seg=[2, 3], lim=4:  [([0, 0], 0..1), ([0, 1], 1..2), ([1, 0], 2..3), ([1, 1], 3..4)]
+/// ```
+///
+/// `0..lim` is divided into `seg[0]` segments as [`rngs`] divides it, each
+/// of those into `seg[1]` segments, and so on. Leaves are yielded in
+/// depth-first order. See [`NestItr::lvl`] to walk a single level.
+///
+/// # Arguments
+///
+/// * `seg` - The number of segments to divide each parent segment, from
+///   the outermost level.
+///
+/// * `lim` - The total number of elements.
+///
+/// # Panics
+///
+/// Panics if `seg` is empty or any `seg` is zero.
+pub fn nested_rngs<const N: usize>(seg: &[usize; N], lim: usize) -> NestItr<N> {
+    assert!(N != 0, "seg must be non-empty");
+    assert!(seg.iter().all(|&seg| seg != 0), "seg must be non-zero");
+    NestItr {
+        seg: *seg,
+        lim,
+        lvl: N - 1,
+        pth: [0; N],
+        stk: vec![rngs(seg[0], lim)],
+    }
+}
+
+/// An iterator over the ranges of a nested partition, with their paths of
+/// segment indexes.
+///
+/// The `serde` feature does not cover this iterator; serialize its
+/// [`Cursor`] instead.
+#[derive(Debug, Clone)]
+pub struct NestItr<const N: usize> {
+    seg: [usize; N],
+    lim: usize,
+    lvl: usize,
+    pth: [usize; N],
+    stk: Vec<RngItr>,
+}
+impl<const N: usize> NestItr<N> {
+    /// Returns an iterator over the ranges of level `lvl` only, from the
+    /// first range.
+    ///
+    /// ```text
+    /// seg=[2, 2], lim=10, lvl=0: [([0, 0], 0..5), ([1, 0], 5..10)]
+    /// ```
+    ///
+    /// Path indexes below `lvl` are zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lvl` is not below `N`.
+    pub fn lvl(self, lvl: usize) -> Self {
+        assert!(lvl < N, "lvl must be below the level count");
+        NestItr {
+            lvl,
+            pth: [0; N],
+            stk: vec![rngs(self.seg[0], self.lim)],
+            ..self
+        }
+    }
+}
+impl<const N: usize> Iterator for NestItr<N> {
+    type Item = ([usize; N], Range<usize>);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let dpt = self.stk.len().checked_sub(1)?;
+            let idx = self.stk[dpt].idx;
+            match self.stk[dpt].next() {
+                None => {
+                    self.stk.pop();
+                }
+                Some(rng) => {
+                    self.pth[dpt] = idx;
+                    if dpt == self.lvl {
+                        return Some((self.pth, rng));
+                    }
+                    self.stk.push(rngs_rng(self.seg[dpt + 1], rng));
+                }
+            }
+        }
+    }
+}
+impl<const N: usize> FusedIterator for NestItr<N> {}
+
+/// A compact, versioned snapshot of a partition iterator's position.
+///
+/// ```text
+/// rngs(4, 10) after one segment: "1.rng.0.10.4.0.1.4"
+/// ```
+///
+/// A cursor holds the partition plan and the remaining segment indexes,
+/// so the iterator's `resume` reconstructs it exactly from the next
+/// segment. The cursors of [`BndItr`] and [`LeafItr`] hold the remaining
+/// bounds or nodes instead, as they have no compact plan. It formats as
+/// `VER.kind.val.val...` with [`fmt::Display`] and parses with
+/// [`str::parse`]. Cursors of another version are rejected.
+///
+/// Values are unsigned decimal integers. An offset of a signed index type
+/// is written as its two's complement in 128 bits, so `-5` is written as
+/// `2^128 - 5`:
+///
+/// ```text
+/// rngs_rng(3, -5i64..5): "1.rng.340282366920938463463374607431768211451.10.3.0.0.3"
+/// ```
+///
+/// With the `serde` feature, a cursor serializes as this string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Cursor {
+    knd: CursorKind,
+    val: Vec<u128>,
+}
+impl Cursor {
+    /// The version of the cursor format.
+    pub const VER: u32 = 1;
+
+    /// Returns the values of a cursor of kind `knd`.
+    fn vals(&self, knd: CursorKind) -> Result<&[u128], CursorError> {
+        if self.knd == knd {
+            Ok(&self.val)
+        } else {
+            Err(CursorError::Knd)
+        }
+    }
+}
+impl fmt::Display for Cursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", Cursor::VER, self.knd.tag())?;
+        self.val.iter().try_for_each(|val| write!(f, ".{val}"))
+    }
+}
+impl std::str::FromStr for Cursor {
+    type Err = CursorError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut prts = s.split('.');
+        let ver: u32 = prts
+            .next()
+            .and_then(|ver| ver.parse().ok())
+            .ok_or(CursorError::Fmt)?;
+        if ver != Cursor::VER {
+            return Err(CursorError::Ver(ver));
+        }
+        let knd = prts
+            .next()
+            .and_then(CursorKind::from_tag)
+            .ok_or(CursorError::Fmt)?;
+        let val = prts
+            .map(|val| val.parse().map_err(|_| CursorError::Fmt))
+            .collect::<Result<_, _>>()?;
+        Ok(Cursor { knd, val })
+    }
+}
+
+/// The iterator a [`Cursor`] was taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum CursorKind {
+    Rng,
+    RngInc,
+    Bnd,
+    BlkCyc,
+    Tile,
+    Aln,
+    Halo,
+    Nest,
+    Str,
+    Leaf,
+    Chunk,
+}
+impl CursorKind {
+    const ALL: [CursorKind; 11] = [
+        CursorKind::Rng,
+        CursorKind::RngInc,
+        CursorKind::Bnd,
+        CursorKind::BlkCyc,
+        CursorKind::Tile,
+        CursorKind::Aln,
+        CursorKind::Halo,
+        CursorKind::Nest,
+        CursorKind::Str,
+        CursorKind::Leaf,
+        CursorKind::Chunk,
+    ];
+
+    /// Returns the tag written in the cursor format.
+    fn tag(self) -> &'static str {
+        match self {
+            CursorKind::Rng => "rng",
+            CursorKind::RngInc => "inc",
+            CursorKind::Bnd => "bnd",
+            CursorKind::BlkCyc => "cyc",
+            CursorKind::Tile => "tile",
+            CursorKind::Aln => "aln",
+            CursorKind::Halo => "halo",
+            CursorKind::Nest => "nest",
+            CursorKind::Str => "str",
+            CursorKind::Leaf => "leaf",
+            CursorKind::Chunk => "chunk",
+        }
+    }
+
+    /// Returns the kind written as `tag`.
+    fn from_tag(tag: &str) -> Option<Self> {
+        CursorKind::ALL.into_iter().find(|knd| knd.tag() == tag)
+    }
+}
+
+/// An error returned when a [`Cursor`] cannot be parsed or resumed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorError {
+    /// The cursor is not in the cursor format.
+    Fmt,
+    /// The cursor format version is not [`Cursor::VER`].
+    Ver(u32),
+    /// The cursor was taken from another kind of iterator.
+    Knd,
+    /// The cursor values do not describe a valid iterator.
+    Val,
+}
+impl fmt::Display for CursorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CursorError::Fmt => write!(f, "cursor is malformed"),
+            CursorError::Ver(ver) => {
+                write!(f, "cursor version {ver} is not {}", Cursor::VER)
+            }
+            CursorError::Knd => write!(f, "cursor is for another iterator"),
+            CursorError::Val => write!(f, "cursor values are invalid"),
+        }
+    }
+}
+impl Error for CursorError {}
+
+/// Returns `val` as a `usize`, or [`CursorError::Val`] if it does not fit.
+fn cur_usz(val: u128) -> Result<usize, CursorError> {
+    usize::try_from(val).map_err(|_| CursorError::Val)
+}
+
+/// Returns the code of `rem` in a [`Cursor`].
+fn rem_cod(rem: RemainderPolicy) -> u128 {
+    match rem {
+        RemainderPolicy::Front => 0,
+        RemainderPolicy::Back => 1,
+        RemainderPolicy::Spread => 2,
+        RemainderPolicy::Last => 3,
+    }
+}
+
+/// Returns the policy with code `cod` in a [`Cursor`].
+fn rem_from_cod(cod: u128) -> Result<RemainderPolicy, CursorError> {
+    match cod {
+        0 => Ok(RemainderPolicy::Front),
+        1 => Ok(RemainderPolicy::Back),
+        2 => Ok(RemainderPolicy::Spread),
+        3 => Ok(RemainderPolicy::Last),
+        _ => Err(CursorError::Val),
+    }
+}
+
+/// Returns the offset `val` as an `I`, or [`CursorError::Val`] if `val`
+/// is not an `I` or `len` elements from it overflow `I`.
+fn cur_off<I>(val: u128, len: u128) -> Result<I, CursorError>
+where
+    I: Idx + AsPrimitive<u128>,
+    u128: AsPrimitive<I>,
+{
+    let off: I = val.as_();
+    let end = off.fwd(len);
+    if off.as_() == val && off <= end && off.dst(end) == len {
+        Ok(off)
+    } else {
+        Err(CursorError::Val)
+    }
+}
+
+/// Returns the position `idx..end` of a cursor over `cnt` segments.
+fn cur_pos(idx: u128, end: u128, cnt: usize) -> Result<(usize, usize), CursorError> {
+    let (idx, end) = (cur_usz(idx)?, cur_usz(end)?);
+    if idx <= end && end <= cnt {
+        Ok((idx, end))
+    } else {
+        Err(CursorError::Val)
+    }
+}
+
+impl<I> RngItr<I>
+where
+    I: Idx + AsPrimitive<u128>,
+    u128: AsPrimitive<I>,
+{
+    /// Returns a cursor at the next segment.
+    ///
+    /// Values are `off, lim, seg, rem, idx, end`, where `rem` is the
+    /// [`RemainderPolicy`] in declaration order from zero.
+    pub fn cursor(&self) -> Cursor {
+        Cursor {
+            knd: CursorKind::Rng,
+            val: self.cur_vals().to_vec(),
+        }
+    }
+
+    /// Returns the iterator resumed from `cur`.
+    pub fn resume(cur: &Cursor) -> Result<Self, CursorError> {
+        RngItr::from_cur_vals(cur.vals(CursorKind::Rng)?)
+    }
+
+    /// Returns the cursor values of the iterator.
+    fn cur_vals(&self) -> [u128; 6] {
+        let prt = &self.prt;
+        [
+            prt.off.as_(),
+            prt.bnd(prt.cnt),
+            prt.cnt as u128,
+            rem_cod(prt.rem),
+            self.idx as u128,
+            self.end as u128,
+        ]
+    }
+
+    /// Returns the iterator with cursor values `val`.
+    fn from_cur_vals(val: &[u128]) -> Result<Self, CursorError> {
+        let &[off, len, cnt, rem, idx, end] = val else {
+            return Err(CursorError::Val);
+        };
+        let cnt = cur_usz(cnt)?;
+        if cnt == 0 {
+            return Err(CursorError::Val);
+        }
+        let (idx, end) = cur_pos(idx, end, cnt)?;
+        let prt = Partition::new(cur_off(off, len)?, cnt, len).rem_pol(rem_from_cod(rem)?);
+        Ok(RngItr { prt, idx, end })
+    }
+}
+
+impl<I> RngIncItr<I>
+where
+    I: Idx + AsPrimitive<u128>,
+    u128: AsPrimitive<I>,
+{
+    /// Returns a cursor at the next segment.
+    ///
+    /// Values are `lo, hi - lo, seg, rem, idx, end`, where `rem` is the
+    /// [`RemainderPolicy`] in declaration order from zero. An empty
+    /// iterator has a zero `seg`.
+    pub fn cursor(&self) -> Cursor {
+        let prt = &self.itr.prt;
+        let lst = match prt.cnt {
+            0 => 0,
+            cnt => prt.off.dst(*self.rng(cnt - 1).end()),
+        };
+        Cursor {
+            knd: CursorKind::RngInc,
+            val: vec![
+                prt.off.as_(),
+                lst,
+                prt.cnt as u128,
+                rem_cod(prt.rem),
+                self.itr.idx as u128,
+                self.itr.end as u128,
+            ],
+        }
+    }
+
+    /// Returns the iterator resumed from `cur`.
+    pub fn resume(cur: &Cursor) -> Result<Self, CursorError> {
+        let &[lo, lst, cnt, rem, idx, end] = cur.vals(CursorKind::RngInc)? else {
+            return Err(CursorError::Val);
+        };
+        let (lo, cnt) = (cur_off::<I>(lo, lst)?, cur_usz(cnt)?);
+        if cnt as u128 > lst.saturating_add(1) {
+            return Err(CursorError::Val);
+        }
+        let (idx, end) = cur_pos(idx, end, cnt)?;
+        let mut itr = match cnt {
+            0 if lst == 0 => RngIncItr {
+                itr: Partition {
+                    off: lo,
+                    cnt: 0,
+                    stp: 0,
+                    stp_adj: 0,
+                    rem: RemainderPolicy::Front,
+                }
+                .iter(),
+            },
+            0 => return Err(CursorError::Val),
+            cnt => rngs_rng_inc(cnt, lo..=lo.fwd(lst)),
+        }
+        .rem_pol(rem_from_cod(rem)?);
+        (itr.itr.idx, itr.itr.end) = (idx, end);
+        Ok(itr)
+    }
+}
+
+impl BndItr {
+    /// Returns a cursor at the next segment.
+    ///
+    /// Values are the bounds of the remaining segments. The bounds are not
+    /// derived from a plan, so unlike other cursors this one grows with
+    /// the number of segments.
+    pub fn cursor(&self) -> Cursor {
+        let bnds = self.bnds.get(self.idx..=self.end).unwrap_or_default();
+        Cursor {
+            knd: CursorKind::Bnd,
+            val: bnds.iter().map(|&bnd| bnd as u128).collect(),
+        }
+    }
+
+    /// Returns the iterator resumed from `cur`.
+    pub fn resume(cur: &Cursor) -> Result<Self, CursorError> {
+        let bnds = cur
+            .vals(CursorKind::Bnd)?
+            .iter()
+            .map(|&bnd| cur_usz(bnd))
+            .collect::<Result<Vec<usize>, _>>()?;
+        if bnds.windows(2).all(|bnds| bnds[0] <= bnds[1]) {
+            Ok(BndItr::new(bnds))
+        } else {
+            Err(CursorError::Val)
+        }
+    }
+}
+
+impl BlkCycItr {
+    /// Returns a cursor at the next block.
+    ///
+    /// Values are `seg, blk, lim, wkr, idx, end`.
+    pub fn cursor(&self) -> Cursor {
+        let dst = &self.dst;
+        Cursor {
+            knd: CursorKind::BlkCyc,
+            val: [dst.seg, dst.blk, dst.lim, self.wkr, self.idx, self.end]
+                .map(|val| val as u128)
+                .to_vec(),
+        }
+    }
+
+    /// Returns the iterator resumed from `cur`.
+    pub fn resume(cur: &Cursor) -> Result<Self, CursorError> {
+        let &[seg, blk, lim, wkr, idx, end] = cur.vals(CursorKind::BlkCyc)? else {
+            return Err(CursorError::Val);
+        };
+        let [seg, blk, lim, wkr] = [seg, blk, lim, wkr].map(cur_usz);
+        let (seg, blk, lim, wkr) = (seg?, blk?, lim?, wkr?);
+        if seg == 0 || blk == 0 || wkr >= seg {
+            return Err(CursorError::Val);
+        }
+        let mut itr = blk_cyc(seg, blk, lim).rngs(wkr);
+        (itr.idx, itr.end) = cur_pos(idx, end, itr.end)?;
+        Ok(itr)
+    }
+}
+
+impl<const N: usize> TileItr<N> {
+    /// Returns a cursor at the next tile.
+    ///
+    /// Values are `ord, idx, end`, then `seg, lim` for each axis, where
+    /// `ord` is the [`TileOrd`] in declaration order from zero.
+    pub fn cursor(&self) -> Cursor {
+        let ord = match self.ord {
+            TileOrd::RowMaj => 0,
+            TileOrd::ColMaj => 1,
+        };
+        let axs = self
+            .axs
+            .iter()
+            .flat_map(|ax| [ax.cnt as u128, ax.bnd(ax.cnt)]);
+        Cursor {
+            knd: CursorKind::Tile,
+            val: [ord, self.idx as u128, self.end as u128]
+                .into_iter()
+                .chain(axs)
+                .collect(),
+        }
+    }
+
+    /// Returns the iterator resumed from `cur`, which must have `N` axes.
+    pub fn resume(cur: &Cursor) -> Result<Self, CursorError> {
+        let val = cur.vals(CursorKind::Tile)?;
+        if val.len() != 3 + 2 * N {
+            return Err(CursorError::Val);
+        }
+        let ord = match val[0] {
+            0 => TileOrd::RowMaj,
+            1 => TileOrd::ColMaj,
+            _ => return Err(CursorError::Val),
+        };
+        let mut seg = [0; N];
+        let mut lim = [0; N];
+        for ax in 0..N {
+            (seg[ax], lim[ax]) = (cur_usz(val[3 + 2 * ax])?, cur_usz(val[4 + 2 * ax])?);
+        }
+        let cnt = seg
+            .iter()
+            .zip(&lim)
+            .map(|(&seg, &lim)| (seg != 0 && seg <= lim.max(1)).then_some(seg))
+            .try_fold(1usize, |cnt, seg| cnt.checked_mul(seg?))
+            .ok_or(CursorError::Val)?;
+        let mut itr = tiles_ord(&seg, &lim, ord);
+        (itr.idx, itr.end) = cur_pos(val[1], val[2], cnt)?;
+        Ok(itr)
+    }
+}
+
+impl AlnItr {
+    /// Returns a cursor at the next segment.
+    ///
+    /// Values are `seg, lim, aln, idx, end`.
+    pub fn cursor(&self) -> Cursor {
+        Cursor {
+            knd: CursorKind::Aln,
+            val: [
+                self.itr.prt.cnt,
+                self.lim,
+                self.aln,
+                self.itr.idx,
+                self.itr.end,
+            ]
+            .map(|val| val as u128)
+            .to_vec(),
+        }
+    }
+
+    /// Returns the iterator resumed from `cur`.
+    pub fn resume(cur: &Cursor) -> Result<Self, CursorError> {
+        let &[seg, lim, aln, idx, end] = cur.vals(CursorKind::Aln)? else {
+            return Err(CursorError::Val);
+        };
+        let (seg, lim, aln) = (cur_usz(seg)?, cur_usz(lim)?, cur_usz(aln)?);
+        if seg == 0 || aln == 0 {
+            return Err(CursorError::Val);
+        }
+        let mut itr = rngs_aln(seg, lim, aln);
+        if itr.itr.prt.cnt != seg {
+            return Err(CursorError::Val);
+        }
+        (itr.itr.idx, itr.itr.end) = cur_pos(idx, end, seg)?;
+        Ok(itr)
+    }
+}
+
+impl<I> HaloItr<I>
+where
+    I: Idx + AsPrimitive<u128>,
+    u128: AsPrimitive<I>,
+{
+    /// Returns a cursor at the next segment.
+    ///
+    /// Values are those of [`RngItr::cursor`], then `lft, rgt`.
+    pub fn cursor(&self) -> Cursor {
+        let mut val = self.itr.cur_vals().to_vec();
+        val.extend([self.lft, self.rgt]);
+        Cursor {
+            knd: CursorKind::Halo,
+            val,
+        }
+    }
+
+    /// Returns the iterator resumed from `cur`.
+    pub fn resume(cur: &Cursor) -> Result<Self, CursorError> {
+        let val = cur.vals(CursorKind::Halo)?;
+        let [lft, rgt] = val.get(6..).unwrap_or_default() else {
+            return Err(CursorError::Val);
+        };
+        let itr = RngItr::from_cur_vals(&val[..6])?;
+        Ok(itr.halo(cur_usz(*lft)?, cur_usz(*rgt)?))
+    }
+}
+
+impl<const N: usize> NestItr<N> {
+    /// Returns a cursor at the next range.
+    ///
+    /// Values are `lim, lvl`, then `seg` for each level, then the index of
+    /// the next segment at each level entered, from the outermost.
+    pub fn cursor(&self) -> Cursor {
+        let hdr = [self.lim, self.lvl].into_iter().chain(self.seg);
+        Cursor {
+            knd: CursorKind::Nest,
+            val: hdr
+                .chain(self.stk.iter().map(|itr| itr.idx))
+                .map(|val| val as u128)
+                .collect(),
+        }
+    }
+
+    /// Returns the iterator resumed from `cur`, which must have `N` levels.
+    pub fn resume(cur: &Cursor) -> Result<Self, CursorError> {
+        let val = cur.vals(CursorKind::Nest)?;
+        if val.len() < 2 + N {
+            return Err(CursorError::Val);
+        }
+        let (lim, lvl) = (cur_usz(val[0])?, cur_usz(val[1])?);
+        let mut seg = [0; N];
+        for (seg, &val) in seg.iter_mut().zip(&val[2..]) {
+            *seg = cur_usz(val)?;
+        }
+        let idxs = &val[2 + N..];
+        if lvl >= N || idxs.len() > lvl + 1 || seg.contains(&0) {
+            return Err(CursorError::Val);
+        }
+        let mut itr = NestItr {
+            seg,
+            lim,
+            lvl,
+            pth: [0; N],
+            stk: Vec::with_capacity(idxs.len()),
+        };
+        for (dpt, &idx) in idxs.iter().enumerate() {
+            let mut lvl_itr = match dpt {
+                0 => rngs(seg[0], lim),
+                dpt => {
+                    // Every level below the innermost has yielded its parent.
+                    let prn = itr.pth[dpt - 1];
+                    rngs_rng(seg[dpt], itr.stk[dpt - 1].rng(prn))
+                }
+            };
+            lvl_itr.idx = cur_pos(idx, lvl_itr.end as u128, lvl_itr.end)?.0;
+            if dpt + 1 < idxs.len() {
+                itr.pth[dpt] = lvl_itr.idx.checked_sub(1).ok_or(CursorError::Val)?;
+            }
+            itr.stk.push(lvl_itr);
+        }
+        Ok(itr)
+    }
+}
+
+impl<'a> StrItr<'a> {
+    /// Returns a cursor at the next piece.
+    ///
+    /// Values are `seg, bnd, idx, end`, where `bnd` is the [`StrBnd`] in
+    /// declaration order from zero.
+    pub fn cursor(&self) -> Cursor {
+        let bnd = match self.bnd {
+            StrBnd::Char => 0,
+            StrBnd::Ws => 1,
+            StrBnd::Word => 2,
+        };
+        Cursor {
+            knd: CursorKind::Str,
+            val: vec![
+                self.seg as u128,
+                bnd,
+                self.itr.idx as u128,
+                self.itr.end as u128,
+            ],
+        }
+    }
+
+    /// Returns the iterator over pieces of `s` resumed from `cur`, which
+    /// must have been taken over the same string.
+    ///
+    /// The pieces are found again, which takes time linear in `s`.
+    pub fn resume(s: &'a str, cur: &Cursor) -> Result<Self, CursorError> {
+        let &[seg, bnd, idx, end] = cur.vals(CursorKind::Str)? else {
+            return Err(CursorError::Val);
+        };
+        let bnd = match bnd {
+            0 => StrBnd::Char,
+            1 => StrBnd::Ws,
+            2 => StrBnd::Word,
+            _ => return Err(CursorError::Val),
+        };
+        let seg = cur_usz(seg)?;
+        if seg == 0 {
+            return Err(CursorError::Val);
+        }
+        let mut itr = strs(seg, s, bnd);
+        (itr.itr.idx, itr.itr.end) = cur_pos(idx, end, itr.itr.end)?;
+        Ok(itr)
+    }
+}
+
+impl LeafItr {
+    /// Returns a cursor at the next leaf.
+    ///
+    /// Values are `ary, leaf, lim, ord`, then `start, end` of each node
+    /// still to be visited, where `ord` is the [`TreeOrd`] in declaration
+    /// order from zero. Depth first, these are the right siblings along
+    /// one path, but breadth first they may span a whole level of the
+    /// tree, so the cursor grows with the number of leaves.
+    pub fn cursor(&self) -> Cursor {
+        let tree = &self.tree;
+        let ord = match self.ord {
+            TreeOrd::Dfs => 0,
+            TreeOrd::Bfs => 1,
+        };
+        let nds = self
+            .nds
+            .iter()
+            .flat_map(|nd| [nd.start as u128, nd.end as u128]);
+        Cursor {
+            knd: CursorKind::Leaf,
+            val: [tree.ary as u128, tree.leaf as u128, tree.lim as u128, ord]
+                .into_iter()
+                .chain(nds)
+                .collect(),
+        }
+    }
+
+    /// Returns the iterator resumed from `cur`.
+    pub fn resume(cur: &Cursor) -> Result<Self, CursorError> {
+        let val = cur.vals(CursorKind::Leaf)?;
+        let (Some(&[ary, leaf, lim, ord]), Some(nds)) = (val.get(..4), val.get(4..)) else {
+            return Err(CursorError::Val);
+        };
+        let (ary, leaf, lim) = (cur_usz(ary)?, cur_usz(leaf)?, cur_usz(lim)?);
+        let ord = match ord {
+            0 => TreeOrd::Dfs,
+            1 => TreeOrd::Bfs,
+            _ => return Err(CursorError::Val),
+        };
+        if ary < 2 || leaf == 0 || nds.len() % 2 != 0 {
+            return Err(CursorError::Val);
+        }
+        let tree = rng_tree(ary, leaf, lim);
+        let nds = nds
+            .chunks(2)
+            .map(|nd| {
+                let nd = cur_usz(nd[0])?..cur_usz(nd[1])?;
+                tree.depth_of(&nd).map(|_| nd).ok_or(CursorError::Val)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(LeafItr { tree, ord, nds })
+    }
+}
+
+impl ChunkDispenser {
+    /// Returns a cursor at the next chunk.
+    ///
+    /// Values are `lim, wkrs, sch, len, cur`, where `sch` is the
+    /// [`ChunkSchedule`] in declaration order from zero and `len` its
+    /// length. Chunks already taken are not dispensed again on resuming,
+    /// even if their workers have not finished them.
+    pub fn cursor(&self) -> Cursor {
+        let (sch, len) = match self.sch {
+            ChunkSchedule::Fixed(len) => (0, len),
+            ChunkSchedule::Guided(len) => (1, len),
+            ChunkSchedule::Factoring(len) => (2, len),
+        };
+        Cursor {
+            knd: CursorKind::Chunk,
+            val: [
+                self.lim,
+                self.wkrs,
+                sch,
+                len,
+                self.cur.load(Ordering::Relaxed),
+            ]
+            .map(|val| val as u128)
+            .to_vec(),
+        }
+    }
+
+    /// Returns the dispenser resumed from `cur`.
+    pub fn resume(cur: &Cursor) -> Result<Self, CursorError> {
+        let &[lim, wkrs, sch, len, pos] = cur.vals(CursorKind::Chunk)? else {
+            return Err(CursorError::Val);
+        };
+        let [lim, wkrs, len, pos] = [lim, wkrs, len, pos].map(cur_usz);
+        let (lim, wkrs, len, pos) = (lim?, wkrs?, len?, pos?);
+        let sch = match sch {
+            0 if len != 0 => ChunkSchedule::Fixed(len),
+            1 => ChunkSchedule::Guided(len),
+            2 => ChunkSchedule::Factoring(len),
+            _ => return Err(CursorError::Val),
+        };
+        if wkrs == 0 || pos > lim {
+            return Err(CursorError::Val);
+        }
+        let dsp = ChunkDispenser::new(lim, wkrs, sch);
+        dsp.cur.store(pos, Ordering::Relaxed);
+        Ok(dsp)
+    }
+}
+
+/// Returns an iterator which generates random integers.
+///
+/// Generates equal quantities of integers represented
+/// by 1-byte, 2-bytes, up to n-bytes.
+///
+/// Generates an infinite number of integers.
+pub fn rnds_eql_byt<T>() -> RndEqlBytItr<T>
+where
+    T: AsPrimitive<T>,
+    usize: num::traits::AsPrimitive<T>,
+{
+    RndEqlBytItr {
+        rng: thread_rng(),
+        byt: 0,
+        phn: PhantomData,
+    }
+}
+/// An iterator generating random integers.
+#[derive(Debug, Clone)]
+pub struct RndEqlBytItr<T>
+where
+    T: AsPrimitive<T>,
+    usize: num::traits::AsPrimitive<T>,
+{
+    rng: ThreadRng,
+    byt: usize,
+    phn: PhantomData<T>,
+}
+
+impl<T> Iterator for RndEqlBytItr<T>
+where
+    T: AsPrimitive<T>,
+    usize: num::traits::AsPrimitive<T>,
+{
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        // Generate a random integer with `byt + 1` number of bytes.
+
+        // Determine min inclusive integer.
+        let lo: usize = if self.byt == 0 {
+            0
+        } else {
+            1 << (self.byt * 8)
+        };
+
+        // Determine max inclusive integer.
+        // Use u128 to allow shifting (1<<64)-1 for 64-bit integer.
+        let hi_inc: usize = ((1u128 << ((self.byt + 1) * 8) as u128) - 1) as usize;
+
+        // Generate the random integer.
+        let ret: usize = self.rng.gen_range(lo..=hi_inc);
+
+        // Prepare for the next iteration.
+        self.byt = (self.byt + 1) % mem::size_of::<Self::Item>();
+
+        Some(ret.as_())
+    }
+}
+
+#[cfg(feature = "rayon")]
+pub use ryn::ParRngItr;
+
+/// Rayon parallel iterators over range segments.
+#[cfg(feature = "rayon")]
+mod ryn {
+    use super::{Idx, RngItr};
+    use rayon::iter::plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer};
+    use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+    use std::ops::Range;
+
+    /// A parallel range iterator.
+    ///
+    /// Segments are split across the rayon pool by segment index, so
+    /// collected results keep the order of [`RngItr`].
+    #[derive(Debug, Clone)]
+    pub struct ParRngItr<I = usize> {
+        itr: RngItr<I>,
+    }
+
+    impl<I: Idx + Send> IntoParallelIterator for RngItr<I> {
+        type Iter = ParRngItr<I>;
+        type Item = Range<I>;
+        fn into_par_iter(self) -> Self::Iter {
+            ParRngItr { itr: self }
+        }
+    }
+
+    impl<I: Idx + Send> ParallelIterator for ParRngItr<I> {
+        type Item = Range<I>;
+        fn drive_unindexed<C>(self, consumer: C) -> C::Result
+        where
+            C: UnindexedConsumer<Self::Item>,
+        {
+            bridge(self, consumer)
+        }
+        fn opt_len(&self) -> Option<usize> {
+            Some(self.itr.len())
+        }
+    }
+
+    impl<I: Idx + Send> IndexedParallelIterator for ParRngItr<I> {
+        fn len(&self) -> usize {
+            self.itr.len()
+        }
+        fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+            bridge(self, consumer)
+        }
+        fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+            callback.callback(self.itr)
+        }
+    }
+
+    impl<I: Idx + Send> Producer for RngItr<I> {
+        type Item = Range<I>;
+        type IntoIter = Self;
+        fn into_iter(self) -> Self::IntoIter {
+            self
+        }
+        fn split_at(self, idx: usize) -> (Self, Self) {
+            let mid = self.idx + idx;
+            (RngItr { end: mid, ..self }, RngItr { idx: mid, ..self })
+        }
+    }
+}
+
+/// Serde support for partition plans.
+#[cfg(feature = "serde")]
+mod srd {
+    use super::{
+        rem_cod, AlnItr, BlkCyc, BndItr, Cursor, CursorKind, HaloItr, Idx, Partition,
+        RemainderPolicy, RngIncItr, RngItr, RngTree, TileItr, TileOrd,
+    };
+    use num::traits::AsPrimitive;
+    use serde::de::{self, Deserializer};
+    use serde::ser::Serializer;
+    use serde::{Deserialize, Serialize};
+
+    /// The serialized form of a [`Partition`].
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "Partition")]
+    struct PrtDef<I> {
+        off: I,
+        lim: u128,
+        seg: usize,
+        #[serde(default)]
+        rem: RemainderPolicy,
+    }
+
+    /// The serialized form of a [`RngItr`].
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "RngItr")]
+    struct RngItrDef<I> {
+        off: I,
+        lim: u128,
+        seg: usize,
+        #[serde(default)]
+        rem: RemainderPolicy,
+        idx: usize,
+        end: usize,
+    }
+
+    /// The serialized form of a [`BlkCyc`].
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "BlkCyc")]
+    struct BlkCycDef {
+        seg: usize,
+        blk: usize,
+        lim: usize,
+    }
+
+    /// The serialized form of a [`RngTree`].
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "RngTree")]
+    struct RngTreeDef {
+        ary: usize,
+        leaf: usize,
+        lim: usize,
+    }
+
+    /// The serialized form of a [`RngIncItr`].
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "RngIncItr")]
+    struct RngIncItrDef<I> {
+        lo: I,
+        hi: I,
+        seg: usize,
+        #[serde(default)]
+        rem: RemainderPolicy,
+        idx: usize,
+        end: usize,
+    }
+
+    /// The serialized form of a [`HaloItr`].
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "HaloItr")]
+    struct HaloItrDef<I> {
+        off: I,
+        lim: u128,
+        seg: usize,
+        #[serde(default)]
+        rem: RemainderPolicy,
+        idx: usize,
+        end: usize,
+        lft: u128,
+        rgt: u128,
+    }
+
+    /// The serialized form of an [`AlnItr`].
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "AlnItr")]
+    struct AlnItrDef {
+        seg: usize,
+        lim: usize,
+        aln: usize,
+        idx: usize,
+        end: usize,
+    }
+
+    /// The serialized form of a [`TileItr`].
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "TileItr")]
+    struct TileItrDef {
+        seg: Vec<usize>,
+        lim: Vec<usize>,
+        #[serde(default)]
+        ord: TileOrd,
+        idx: usize,
+        end: usize,
+    }
+
+    /// The serialized form of a [`BndItr`].
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "BndItr")]
+    struct BndItrDef {
+        bnds: Vec<usize>,
+    }
+
+    /// Returns the partition of `lim` elements from `off` into `seg`
+    /// segments, or `None` if `seg` is zero or the elements overflow `I`.
+    fn prt<I: Idx>(off: I, lim: u128, seg: usize, rem: RemainderPolicy) -> Option<Partition<I>> {
+        let end = off.fwd(lim);
+        (seg != 0 && off <= end && off.dst(end) == lim)
+            .then(|| Partition::new(off, seg, lim).rem_pol(rem))
+    }
+
+    impl<I: Idx + Serialize> Serialize for Partition<I> {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            PrtDef {
+                off: self.off,
+                lim: self.bnd(self.cnt),
+                seg: self.cnt,
+                rem: self.rem,
+            }
+            .serialize(s)
+        }
+    }
+
+    impl<'de, I: Idx + Deserialize<'de>> Deserialize<'de> for Partition<I> {
+        fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            let def = PrtDef::deserialize(d)?;
+            prt(def.off, def.lim, def.seg, def.rem)
+                .ok_or_else(|| de::Error::custom("invalid partition"))
+        }
+    }
+
+    impl<I: Idx + Serialize> Serialize for RngItr<I> {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            RngItrDef {
+                off: self.prt.off,
+                lim: self.prt.bnd(self.prt.cnt),
+                seg: self.prt.cnt,
+                rem: self.prt.rem,
+                idx: self.idx,
+                end: self.end,
+            }
+            .serialize(s)
+        }
+    }
+
+    impl<'de, I: Idx + Deserialize<'de>> Deserialize<'de> for RngItr<I> {
+        fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            let def = RngItrDef::deserialize(d)?;
+            let prt = prt(def.off, def.lim, def.seg, def.rem)
+                .ok_or_else(|| de::Error::custom("invalid partition"))?;
+            if def.idx <= def.end && def.end <= def.seg {
+                Ok(RngItr {
+                    prt,
+                    idx: def.idx,
+                    end: def.end,
+                })
+            } else {
+                Err(de::Error::custom("segment position out of bounds"))
+            }
+        }
+    }
+
+    impl<I> Serialize for RngIncItr<I>
+    where
+        I: Idx + AsPrimitive<u128> + Serialize,
+        u128: AsPrimitive<I>,
+    {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            let lo = self.itr.prt.off;
+            let hi = match self.itr.prt.cnt {
+                0 => lo,
+                cnt => *self.rng(cnt - 1).end(),
+            };
+            RngIncItrDef {
+                lo,
+                hi,
+                seg: self.itr.prt.cnt,
+                rem: self.itr.prt.rem,
+                idx: self.itr.idx,
+                end: self.itr.end,
+            }
+            .serialize(s)
+        }
+    }
+
+    impl<'de, I> Deserialize<'de> for RngIncItr<I>
+    where
+        I: Idx + AsPrimitive<u128> + Deserialize<'de>,
+        u128: AsPrimitive<I>,
+    {
+        fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            let def = RngIncItrDef::<I>::deserialize(d)?;
+            if def.hi < def.lo {
+                return Err(de::Error::custom("invalid inclusive partition"));
+            }
+            let cur = Cursor {
+                knd: CursorKind::RngInc,
+                val: vec![
+                    def.lo.as_(),
+                    def.lo.dst(def.hi),
+                    def.seg as u128,
+                    rem_cod(def.rem),
+                    def.idx as u128,
+                    def.end as u128,
+                ],
+            };
+            RngIncItr::resume(&cur).map_err(|_| de::Error::custom("invalid inclusive partition"))
+        }
+    }
+
+    impl<I: Idx + Serialize> Serialize for HaloItr<I> {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            let prt = &self.itr.prt;
+            HaloItrDef {
+                off: prt.off,
+                lim: prt.bnd(prt.cnt),
+                seg: prt.cnt,
+                rem: prt.rem,
+                idx: self.itr.idx,
+                end: self.itr.end,
+                lft: self.lft,
+                rgt: self.rgt,
+            }
+            .serialize(s)
+        }
+    }
+
+    impl<'de, I: Idx + Deserialize<'de>> Deserialize<'de> for HaloItr<I> {
+        fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            let def = HaloItrDef::deserialize(d)?;
+            let prt = prt(def.off, def.lim, def.seg, def.rem)
+                .ok_or_else(|| de::Error::custom("invalid partition"))?;
+            if def.idx <= def.end && def.end <= def.seg {
+                Ok(HaloItr {
+                    itr: RngItr {
+                        prt,
+                        idx: def.idx,
+                        end: def.end,
+                    },
+                    lft: def.lft,
+                    rgt: def.rgt,
+                })
+            } else {
+                Err(de::Error::custom("segment position out of bounds"))
+            }
+        }
+    }
+
+    impl Serialize for AlnItr {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            AlnItrDef {
+                seg: self.itr.prt.cnt,
+                lim: self.lim,
+                aln: self.aln,
+                idx: self.itr.idx,
+                end: self.itr.end,
+            }
+            .serialize(s)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for AlnItr {
+        fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            let def = AlnItrDef::deserialize(d)?;
+            let cur = Cursor {
+                knd: CursorKind::Aln,
+                val: [def.seg, def.lim, def.aln, def.idx, def.end]
+                    .map(|val| val as u128)
+                    .to_vec(),
+            };
+            AlnItr::resume(&cur).map_err(|_| de::Error::custom("invalid aligned partition"))
+        }
+    }
+
+    impl<const N: usize> Serialize for TileItr<N> {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            TileItrDef {
+                seg: self.axs.iter().map(|ax| ax.cnt).collect(),
+                lim: self.axs.iter().map(|ax| ax.bnd(ax.cnt) as usize).collect(),
+                ord: self.ord,
+                idx: self.idx,
+                end: self.end,
+            }
+            .serialize(s)
+        }
+    }
+
+    impl<'de, const N: usize> Deserialize<'de> for TileItr<N> {
+        fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            let def = TileItrDef::deserialize(d)?;
+            if def.seg.len() != N || def.lim.len() != N {
+                return Err(de::Error::custom(format_args!("expected {N} axes")));
+            }
+            let ord = match def.ord {
+                TileOrd::RowMaj => 0,
+                TileOrd::ColMaj => 1,
+            };
+            let axs = def
+                .seg
+                .iter()
+                .zip(&def.lim)
+                .flat_map(|(&seg, &lim)| [seg as u128, lim as u128]);
+            let cur = Cursor {
+                knd: CursorKind::Tile,
+                val: [ord, def.idx as u128, def.end as u128]
+                    .into_iter()
+                    .chain(axs)
+                    .collect(),
+            };
+            TileItr::resume(&cur).map_err(|_| de::Error::custom("invalid tile plan"))
+        }
+    }
+
+    impl Serialize for BndItr {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            let bnds = self.bnds.get(self.idx..=self.end).unwrap_or_default();
+            BndItrDef {
+                bnds: bnds.to_vec(),
+            }
+            .serialize(s)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for BndItr {
+        fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            let def = BndItrDef::deserialize(d)?;
+            if def.bnds.windows(2).all(|bnds| bnds[0] <= bnds[1]) {
+                Ok(BndItr::new(def.bnds))
+            } else {
+                Err(de::Error::custom("bounds must be ascending"))
+            }
+        }
+    }
+
+    impl Serialize for BlkCyc {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            BlkCycDef {
+                seg: self.seg,
+                blk: self.blk,
+                lim: self.lim,
+            }
+            .serialize(s)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for BlkCyc {
+        fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            let def = BlkCycDef::deserialize(d)?;
+            if def.seg != 0 && def.blk != 0 {
+                Ok(BlkCyc {
+                    seg: def.seg,
+                    blk: def.blk,
+                    lim: def.lim,
+                })
+            } else {
+                Err(de::Error::custom("seg and blk must be non-zero"))
+            }
+        }
+    }
+
+    impl Serialize for RngTree {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            RngTreeDef {
+                ary: self.ary,
+                leaf: self.leaf,
+                lim: self.lim,
+            }
+            .serialize(s)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for RngTree {
+        fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            let def = RngTreeDef::deserialize(d)?;
+            if def.ary >= 2 && def.leaf != 0 {
+                Ok(RngTree {
+                    ary: def.ary,
+                    leaf: def.leaf,
+                    lim: def.lim,
+                })
+            } else {
+                Err(de::Error::custom(
+                    "ary must be at least two and leaf non-zero",
+                ))
+            }
+        }
+    }
+
+    impl Serialize for Cursor {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            s.collect_str(self)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Cursor {
+        fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            let s = String::deserialize(d)?;
+            s.parse().map_err(de::Error::custom)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tst {
+    use super::*;
+
+    #[test]
+    fn rngs_n() {
+        assert_eq!(rngs(2, 6).collect::<Vec<Range<usize>>>(), [0..3, 3..6]);
+        assert_eq!(
+            rngs(2, 7).collect::<Vec<Range<usize>>>(),
+            [0..4, 4..7]
+        );
+        assert_eq!(
+            rngs(4, 10).collect::<Vec<Range<usize>>>(),
+            [0..3, 3..6, 6..8, 8..10]
+        );
+    }
+
+    #[test]
+    fn rngs_edg_n() {
+        let mut itr = rngs(3, 0);
+        assert_eq!(itr.next(), Some(0..0));
+        assert_eq!(itr.next(), None);
+        assert_eq!(
+            rngs(10, 3).collect::<Vec<Range<usize>>>(),
+            [0..1, 1..2, 2..3]
+        );
+        let mut itr = rngs(1, 5);
+        assert_eq!(itr.next(), Some(0..5));
+        assert_eq!(itr.next(), None);
+    }
+
+    #[test]
+    fn rngs_rev_n() {
+        assert_eq!(
+            rngs(4, 10).rev().collect::<Vec<Range<usize>>>(),
+            [8..10, 6..8, 3..6, 0..3]
+        );
+        let mut itr = rngs(4, 10);
+        assert_eq!(itr.next(), Some(0..3));
+        assert_eq!(itr.next_back(), Some(8..10));
+        assert_eq!(itr.next(), Some(3..6));
+        assert_eq!(itr.next_back(), Some(6..8));
+        assert_eq!(itr.next(), None);
+        assert_eq!(itr.next_back(), None);
+    }
+
+    #[test]
+    fn rngs_len_n() {
+        assert_eq!(rngs(4, 10).len(), 4);
+        assert_eq!(rngs(10, 3).len(), 3);
+        assert_eq!(rngs(3, 0).len(), 1);
+        let mut itr = rngs(4, 10);
+        itr.next();
+        itr.next_back();
+        assert_eq!(itr.len(), 2);
+        assert_eq!(itr.size_hint(), (2, Some(2)));
+        assert_eq!(rngs(4, 10).count(), 4);
+        assert_eq!(rngs(4, 10).last(), Some(8..10));
+    }
+
+    #[test]
+    fn rngs_nth_n() {
+        let mut itr = rngs(4, 10);
+        assert_eq!(itr.nth(1), Some(3..6));
+        assert_eq!(itr.nth_back(1), Some(6..8));
+        assert_eq!(itr.next(), None);
+
+        let mut itr = rngs(usize::MAX, usize::MAX);
+        assert_eq!(
+            itr.nth(usize::MAX - 2),
+            Some(usize::MAX - 2..usize::MAX - 1)
+        );
+        assert_eq!(itr.next(), Some(usize::MAX - 1..usize::MAX));
+        assert_eq!(itr.next(), None);
+
+        let mut itr = rngs(4, 10);
+        assert_eq!(itr.nth(4), None);
+        assert_eq!(itr.next_back(), None);
+    }
+
+    #[test]
+    fn rngs_step_by_n() {
+        assert_eq!(
+            rngs(5, 10).step_by(2).collect::<Vec<Range<usize>>>(),
+            [0..2, 4..6, 8..10]
+        );
+        assert_eq!(
+            rngs(5, 10).rev().step_by(2).collect::<Vec<Range<usize>>>(),
+            [8..10, 4..6, 0..2]
+        );
+    }
+
+    #[test]
+    fn try_rngs_n() {
+        assert_eq!(
+            try_rngs(4, 10).unwrap().collect::<Vec<Range<usize>>>(),
+            [0..3, 3..6, 6..8, 8..10]
+        );
+        assert_eq!(try_rngs(0, 10).unwrap_err(), RngsError::ZroSeg);
+        assert_eq!(try_rngs(2, 0).unwrap_err(), RngsError::ZroLim);
+        assert_eq!(
+            try_rngs(10, 3).unwrap_err(),
+            RngsError::SegGtLim { seg: 10, lim: 3 }
+        );
+        assert_eq!(try_rngs(0, 0).unwrap_err(), RngsError::ZroSeg);
+    }
+
+    #[test]
+    fn try_rngs_with_n() {
+        for pol in [SegPolicy::Clamp, SegPolicy::AllowEmpty, SegPolicy::Error] {
+            assert_eq!(try_rngs_with(0, 3, pol).unwrap_err(), RngsError::ZroSeg);
+        }
+        assert_eq!(
+            try_rngs_with(4, 3, SegPolicy::Clamp)
+                .unwrap()
+                .collect::<Vec<Range<usize>>>(),
+            [0..1, 1..2, 2..3]
+        );
+        assert_eq!(
+            try_rngs_with(4, 3, SegPolicy::AllowEmpty)
+                .unwrap()
+                .collect::<Vec<Range<usize>>>(),
+            [0..1, 1..2, 2..3, 3..3]
+        );
+        assert_eq!(try_rngs_with(2, 0, SegPolicy::Clamp).unwrap().len(), 1);
+        assert_eq!(
+            try_rngs_with(2, 0, SegPolicy::AllowEmpty)
+                .unwrap()
+                .collect::<Vec<Range<usize>>>(),
+            [0..0, 0..0]
+        );
+        assert_eq!(
+            try_rngs_with(3, 3, SegPolicy::Error)
+                .unwrap()
+                .collect::<Vec<Range<usize>>>(),
+            [0..1, 1..2, 2..3]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn rngs_zro_seg_p() {
+        rngs(0, 10);
+    }
+
+    #[test]
+    fn rngs_by_len_n() {
+        assert_eq!(
+            rngs_by_len(3, 10).collect::<Vec<Range<usize>>>(),
+            [0..4, 4..7, 7..10]
+        );
+        assert_eq!(
+            rngs_by_len(4, 10).collect::<Vec<Range<usize>>>(),
+            [0..4, 4..7, 7..10]
+        );
+        assert_eq!(
+            rngs_by_len(6, 10).collect::<Vec<Range<usize>>>(),
+            [0..5, 5..10]
+        );
+        assert_eq!(rngs_by_len(8, 3).len(), 1);
+        assert_eq!(rngs_by_len(8, 0).len(), 1);
+        assert_eq!(rngs_by_len(1, 5).len(), 5);
+        assert_eq!(rngs_by_len(usize::MAX, usize::MAX).len(), 1);
+        assert_eq!(rngs_by_len(4096, 1_000_000).len(), 244);
+        for rng in rngs_by_len(4096, 1_000_000) {
+            assert!((4096..4096 + 4096 / 2).contains(&rng.len()));
+        }
+    }
+
+    #[test]
+    fn rngs_by_len_bnd_n() {
+        assert_eq!(
+            rngs_by_len_bnd(3, 3, 8, 10).collect::<Vec<Range<usize>>>(),
+            [0..4, 4..7, 7..10]
+        );
+        assert_eq!(
+            rngs_by_len_bnd(2, 3, 8, 10).collect::<Vec<Range<usize>>>(),
+            [0..4, 4..7, 7..10]
+        );
+        assert_eq!(
+            rngs_by_len_bnd(9, 3, 8, 10).collect::<Vec<Range<usize>>>(),
+            [0..5, 5..10]
+        );
+        assert_eq!(
+            rngs_by_len_bnd(9, 6, 8, 10).collect::<Vec<Range<usize>>>(),
+            [0..5, 5..10]
+        );
+        assert_eq!(rngs_by_len_bnd(4, 0, 4, 10).len(), 3);
+        assert_eq!(rngs_by_len_bnd(4, 5, 8, 3).len(), 1);
+        assert_eq!(rngs_by_len_bnd(4, 5, 8, 0).len(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rngs_by_len_zro_p() {
+        rngs_by_len(0, 10);
+    }
+
+    #[test]
+    fn rngs_weighted_n() {
+        assert_eq!(
+            rngs_weighted(&[4, 2, 1, 1], 16).collect::<Vec<Range<usize>>>(),
+            [0..8, 8..12, 12..14, 14..16]
+        );
+        assert_eq!(
+            rngs_weighted(&[4, 2, 1, 1], 10).collect::<Vec<Range<usize>>>(),
+            [0..5, 5..8, 8..9, 9..10]
+        );
+        assert_eq!(
+            rngs_weighted(&[1, 0, 1], 4).collect::<Vec<Range<usize>>>(),
+            [0..2, 2..2, 2..4]
+        );
+        assert_eq!(
+            rngs_weighted(&[1, 1, 1], 2).collect::<Vec<Range<usize>>>(),
+            [0..1, 1..2, 2..2]
+        );
+        assert_eq!(
+            rngs_weighted(&[usize::MAX, usize::MAX], usize::MAX).collect::<Vec<Range<usize>>>(),
+            [0..usize::MAX / 2 + 1, usize::MAX / 2 + 1..usize::MAX]
+        );
+        for lim in 0..40 {
+            for seg in 1..=lim.max(1) {
+                assert_eq!(
+                    rngs_weighted(&vec![3; seg], lim).collect::<Vec<Range<usize>>>(),
+                    rngs(seg, lim).collect::<Vec<Range<usize>>>()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn rngs_weighted_rev_n() {
+        let mut itr = rngs_weighted(&[4, 2, 1, 1], 16);
+        assert_eq!(itr.len(), 4);
+        assert_eq!(itr.next_back(), Some(14..16));
+        assert_eq!(itr.nth(1), Some(8..12));
+        assert_eq!(itr.len(), 1);
+        assert_eq!(itr.next_back(), Some(12..14));
+        assert_eq!(itr.next(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rngs_weighted_zro_p() {
+        rngs_weighted(&[0, 0], 10);
+    }
+
+    #[test]
+    fn rngs_cst_n() {
+        assert_eq!(
+            rngs_cst(2, &[1, 1, 1, 1]).collect::<Vec<Range<usize>>>(),
+            [0..2, 2..4]
+        );
+        assert_eq!(
+            rngs_cst(2, &[9, 1, 1, 1]).collect::<Vec<Range<usize>>>(),
+            [0..1, 1..4]
+        );
+        assert_eq!(
+            rngs_cst(3, &[1, 2, 3, 4, 5]).collect::<Vec<Range<usize>>>(),
+            [0..3, 3..4, 4..5]
+        );
+        assert_eq!(
+            rngs_cst(3, &[0, 0, 0, 0]).collect::<Vec<Range<usize>>>(),
+            [0..2, 2..3, 3..4]
+        );
+        assert_eq!(
+            rngs_cst(4, &[5, 5]).collect::<Vec<Range<usize>>>(),
+            [0..1, 1..2]
+        );
+        let mut itr = rngs_cst(3, &[]);
+        assert_eq!(itr.next(), Some(0..0));
+        assert_eq!(itr.next(), None);
+    }
+
+    #[test]
+    fn rngs_cst_pfx_n() {
+        assert_eq!(
+            rngs_cst_pfx(3, &[1, 3, 6, 10, 15]).collect::<Vec<Range<usize>>>(),
+            rngs_cst(3, &[1, 2, 3, 4, 5]).collect::<Vec<Range<usize>>>()
+        );
+        // Compare the largest segment cost against every split.
+        let cst = [7, 1, 3, 8, 2, 2, 9, 1, 4, 6];
+        let sum = |rng: Range<usize>| cst[rng].iter().sum::<u64>();
+        let max = rngs_cst(3, &cst).map(sum).max().unwrap();
+        let mut bst = u64::MAX;
+        for fst in 1..cst.len() {
+            for snd in fst + 1..cst.len() {
+                bst = bst.min(sum(0..fst).max(sum(fst..snd)).max(sum(snd..cst.len())));
+            }
+        }
+        assert_eq!(max, bst);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rngs_cst_pfx_dec_p() {
+        rngs_cst_pfx(2, &[3, 2, 5]);
+    }
+
+    #[test]
+    fn tiles_n() {
+        assert_eq!(
+            tiles(&[2, 2], &[4, 6]).collect::<Vec<[Range<usize>; 2]>>(),
+            [[0..2, 0..3], [0..2, 3..6], [2..4, 0..3], [2..4, 3..6]]
+        );
+        assert_eq!(
+            tiles_ord(&[2, 2], &[4, 6], TileOrd::ColMaj).collect::<Vec<[Range<usize>; 2]>>(),
+            [[0..2, 0..3], [2..4, 0..3], [0..2, 3..6], [2..4, 3..6]]
+        );
+        let itr = tiles(&[2, 3, 4], &[5, 7, 9]);
+        assert_eq!(itr.len(), 24);
+        let mut cnt = 0;
+        for tile in itr.clone() {
+            cnt += tile.iter().map(|rng| rng.len()).product::<usize>();
+        }
+        assert_eq!(cnt, 5 * 7 * 9);
+        assert_eq!(
+            itr.clone().rev().collect::<Vec<[Range<usize>; 3]>>(),
+            itr.collect::<Vec<[Range<usize>; 3]>>()
+                .into_iter()
+                .rev()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn tiles_nth_n() {
+        let mut itr = tiles(&[3, 3], &[3, 3]);
+        assert_eq!(itr.nth(4), Some([1..2, 1..2]));
+        assert_eq!(itr.nth_back(1), Some([2..3, 1..2]));
+        assert_eq!(itr.len(), 2);
+        assert_eq!(itr.nth(2), None);
+        assert_eq!(tiles(&[2, 5], &[4, 3]).len(), 6);
+    }
+
+    #[test]
+    fn tiles_sqr_n() {
+        let segs = |itr: TileItr<2>| itr.axs.map(|ax| ax.len());
+        assert_eq!(segs(tiles_sqr(4, &[100, 100])), [2, 2]);
+        assert_eq!(segs(tiles_sqr(8, &[400, 100])), [4, 2]);
+        assert_eq!(segs(tiles_sqr(7, &[100, 100])), [1, 7]);
+        assert_eq!(segs(tiles_sqr(6, &[100, 300])), [1, 6]);
+        assert_eq!(
+            tiles_sqr(8, &[10, 10, 10]).axs.map(|ax| ax.len()),
+            [2, 2, 2]
+        );
+        assert_eq!(tiles_sqr(12, &[90]).len(), 12);
+        assert_eq!(segs(tiles_sqr(4, &[0, 100])), [1, 4]);
+        assert_eq!(tiles_sqr(4, &[0, 100]).len(), 4);
+        assert_eq!(segs(tiles_sqr(4, &[0, 0])), [1, 1]);
+        assert_eq!(segs(tiles_sqr(8, &[2, 2])), [2, 2]);
+        assert_eq!(segs(tiles_sqr(5, &[2, 2])), [2, 2]);
+        assert_eq!(segs(tiles_sqr(6, &[3, 100])), [1, 6]);
+        assert_eq!(tiles_sqr(7, &[3]).len(), 3);
+        for (cnt, lim) in [(6, [2, 3]), (9, [3, 50]), (12, [1, 12]), (5, [5, 1])] {
+            assert_eq!(tiles_sqr(cnt, &lim).len(), cnt);
+        }
+    }
+
+    #[test]
+    fn rngs_rng_n() {
+        assert_eq!(
+            rngs_rng(2, 10..16).collect::<Vec<Range<usize>>>(),
+            [10..13, 13..16]
+        );
+        assert_eq!(
+            rngs_rng(3, -5i64..5).collect::<Vec<Range<i64>>>(),
+            [-5..-1, -1..2, 2..5]
+        );
+        assert_eq!(
+            rngs_rng(2, 0u8..255).collect::<Vec<Range<u8>>>(),
+            [0..128, 128..255]
+        );
+        assert_eq!(
+            rngs_rng(4, 1u32 << 31..u32::MAX).collect::<Vec<Range<u32>>>(),
+            [
+                0x8000_0000..0xa000_0000,
+                0xa000_0000..0xc000_0000,
+                0xc000_0000..0xe000_0000,
+                0xe000_0000..u32::MAX
+            ]
+        );
+        assert_eq!(
+            rngs_rng(2, 0u128..u128::MAX).collect::<Vec<Range<u128>>>(),
+            [0..1 << 127, 1 << 127..u128::MAX]
+        );
+        assert_eq!(
+            rngs_rng(2, i128::MIN..i128::MAX).collect::<Vec<Range<i128>>>(),
+            [i128::MIN..0, 0..i128::MAX]
+        );
+        assert_eq!(
+            rngs_rng(2, i8::MIN..i8::MAX).collect::<Vec<Range<i8>>>(),
+            [-128..0, 0..127]
+        );
+        assert_eq!(
+            rngs_rng(4, 20u64..30).collect::<Vec<Range<u64>>>(),
+            rngs(4, 10)
+                .map(|rng| rng.start as u64 + 20..rng.end as u64 + 20)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn rngs_rng_edg_n() {
+        let mut itr = rngs_rng(3, 7u16..7);
+        assert_eq!(itr.next(), Some(7..7));
+        assert_eq!(itr.next(), None);
+        let mut itr = rngs_rng(
+            3,
+            Range {
+                start: 7i32,
+                end: -7,
+            },
+        );
+        assert_eq!(itr.next(), Some(7..7));
+        assert_eq!(itr.next(), None);
+        assert_eq!(
+            rngs_rng(5, -2i16..1).collect::<Vec<Range<i16>>>(),
+            [-2..-1, -1..0, 0..1]
+        );
+        let mut itr = rngs_rng(usize::MAX, 0u64..u64::MAX);
+        assert_eq!(itr.len(), usize::MAX);
+        assert_eq!(itr.next_back(), Some(u64::MAX - 1..u64::MAX));
+        assert_eq!(itr.nth(1 << 40), Some(1 << 40..(1 << 40) + 1));
+    }
+
+    #[test]
+    fn rngs_rng_inc_n() {
+        assert_eq!(
+            rngs_rng_inc(2, 0..=9).collect::<Vec<RangeInclusive<usize>>>(),
+            [0..=4, 5..=9]
+        );
+        assert_eq!(
+            rngs_rng_inc(4, 0..=9).collect::<Vec<RangeInclusive<usize>>>(),
+            [0..=2, 3..=5, 6..=7, 8..=9]
+        );
+        assert_eq!(
+            rngs_rng_inc(3, i8::MIN..=i8::MAX).collect::<Vec<RangeInclusive<i8>>>(),
+            [-128..=-43, -42..=42, 43..=127]
+        );
+        assert_eq!(
+            rngs_rng_inc(4, -6i32..=3).collect::<Vec<RangeInclusive<i32>>>(),
+            [-6..=-4, -3..=-1, 0..=1, 2..=3]
+        );
+    }
+
+    #[test]
+    fn rngs_rng_inc_dom_n() {
+        assert_eq!(
+            rngs_rng_inc(2, 0u64..=u64::MAX).collect::<Vec<RangeInclusive<u64>>>(),
+            [0..=(1 << 63) - 1, 1 << 63..=u64::MAX]
+        );
+        assert_eq!(
+            rngs_rng_inc(1, 0u64..=u64::MAX).collect::<Vec<RangeInclusive<u64>>>(),
+            vec![0..=u64::MAX; 1]
+        );
+        assert_eq!(
+            rngs_rng_inc(3, 0u64..=u64::MAX).collect::<Vec<RangeInclusive<u64>>>(),
+            [
+                0..=0x5555_5555_5555_5555,
+                0x5555_5555_5555_5556..=0xaaaa_aaaa_aaaa_aaaa,
+                0xaaaa_aaaa_aaaa_aaab..=u64::MAX
+            ]
+        );
+        assert_eq!(
+            rngs_rng_inc(2, i64::MIN..=i64::MAX).collect::<Vec<RangeInclusive<i64>>>(),
+            [i64::MIN..=-1, 0..=i64::MAX]
+        );
+        assert_eq!(
+            rngs_rng_inc(2, 0u128..=u128::MAX).collect::<Vec<RangeInclusive<u128>>>(),
+            [0..=(1 << 127) - 1, 1 << 127..=u128::MAX]
+        );
+        assert_eq!(
+            rngs_rng_inc(1, i128::MIN..=i128::MAX).collect::<Vec<RangeInclusive<i128>>>(),
+            vec![i128::MIN..=i128::MAX; 1]
+        );
+        let mut itr = rngs_rng_inc(usize::MAX, 0u128..=u128::MAX);
+        assert_eq!(itr.len(), usize::MAX);
+        assert_eq!(itr.next_back().map(|rng| *rng.end()), Some(u128::MAX));
+        assert_eq!(itr.next().map(|rng| *rng.start()), Some(0));
+        assert_eq!(
+            rngs_rng_inc(300, 0u8..=u8::MAX).collect::<Vec<RangeInclusive<u8>>>(),
+            (0..=u8::MAX).map(|idx| idx..=idx).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn rngs_rng_inc_edg_n() {
+        assert_eq!(
+            rngs_rng_inc(3, u64::MAX..=u64::MAX).collect::<Vec<RangeInclusive<u64>>>(),
+            vec![u64::MAX..=u64::MAX; 1]
+        );
+        assert_eq!(
+            rngs_rng_inc(3, i64::MIN..=i64::MIN + 1).collect::<Vec<RangeInclusive<i64>>>(),
+            [i64::MIN..=i64::MIN, i64::MIN + 1..=i64::MIN + 1]
+        );
+        let mut rng = 3u32..=5;
+        rng.by_ref().for_each(drop);
+        assert_eq!(rngs_rng_inc(2, rng).next(), None);
+        assert_eq!(rngs_rng_inc(2, RangeInclusive::new(5u32, 3)).len(), 0);
+        assert_eq!(
+            rngs_rng(2, 0u64..u64::MAX).collect::<Vec<Range<u64>>>(),
+            [0..1 << 63, 1 << 63..u64::MAX]
+        );
+        assert_eq!(
+            rngs_rng(2, i64::MIN..i64::MAX).collect::<Vec<Range<i64>>>(),
+            [i64::MIN..0, 0..i64::MAX]
+        );
+    }
+
+    #[test]
+    fn rngs_halo_n() {
+        assert_eq!(
+            rngs_halo(3, 9, 1, 2).collect::<Vec<(Range<usize>, Range<usize>)>>(),
+            [(0..3, 0..5), (3..6, 2..8), (6..9, 5..9)]
+        );
+        assert_eq!(
+            rngs_halo(2, 4, 0, 0).collect::<Vec<(Range<usize>, Range<usize>)>>(),
+            [(0..2, 0..2), (2..4, 2..4)]
+        );
+        assert_eq!(
+            rngs_halo(2, 4, usize::MAX, usize::MAX).collect::<Vec<(Range<usize>, Range<usize>)>>(),
+            [(0..2, 0..4), (2..4, 0..4)]
+        );
+        let mut itr = rngs_halo(4, 10, 2, 1);
+        assert_eq!(itr.len(), 4);
+        assert_eq!(itr.next_back(), Some((8..10, 6..10)));
+        assert_eq!(itr.nth(1), Some((3..6, 1..7)));
+        assert_eq!(itr.next(), Some((6..8, 4..9)));
+        assert_eq!(itr.next(), None);
+    }
+
+    #[test]
+    fn rngs_rng_halo_n() {
+        assert_eq!(
+            rngs_rng(3, -6i64..6)
+                .halo(2, 2)
+                .collect::<Vec<(Range<i64>, Range<i64>)>>(),
+            [(-6..-2, -6..0), (-2..2, -4..4), (2..6, 0..6)]
+        );
+        assert_eq!(
+            rngs_rng(2, u64::MAX - 4..u64::MAX)
+                .halo(9, 9)
+                .collect::<Vec<(Range<u64>, Range<u64>)>>(),
+            [
+                (u64::MAX - 4..u64::MAX - 2, u64::MAX - 4..u64::MAX),
+                (u64::MAX - 2..u64::MAX, u64::MAX - 4..u64::MAX)
+            ]
+        );
+        let mut itr = rngs(3, 9);
+        itr.next();
+        assert_eq!(
+            itr.halo(1, 1)
+                .collect::<Vec<(Range<usize>, Range<usize>)>>(),
+            [(3..6, 2..7), (6..9, 5..9)]
+        );
+        let mut itr = rngs(3, 9);
+        itr.next_back();
+        assert_eq!(
+            itr.halo(1, 1)
+                .collect::<Vec<(Range<usize>, Range<usize>)>>(),
+            [(0..3, 0..4), (3..6, 2..7)]
+        );
+    }
+
+    #[test]
+    fn rngs_aln_n() {
+        assert_eq!(
+            rngs_aln(2, 64, 16).collect::<Vec<Range<usize>>>(),
+            [0..32, 32..64]
+        );
+        assert_eq!(
+            rngs_aln(3, 64, 16).collect::<Vec<Range<usize>>>(),
+            [0..32, 32..48, 48..64]
+        );
+        assert_eq!(
+            rngs_aln(2, 50, 16).collect::<Vec<Range<usize>>>(),
+            [0..32, 32..50]
+        );
+        assert_eq!(
+            rngs_aln(4, 20, 16).collect::<Vec<Range<usize>>>(),
+            [0..16, 16..20]
+        );
+        assert_eq!(rngs_aln(3, 64, 16).imb(), 16);
+        assert_eq!(rngs_aln(2, 50, 16).imb(), 14);
+        assert_eq!(rngs_aln(4, 64, 16).imb(), 0);
+        assert_eq!(rngs_aln(4, 0, 16).imb(), 0);
+        assert_eq!(rngs_aln(4, 0, 16).len(), 1);
+        assert_eq!(
+            rngs_aln(2, usize::MAX, 1 << 60).collect::<Vec<Range<usize>>>(),
+            [0..1 << 63, 1 << 63..usize::MAX]
+        );
+        for lim in 0..50 {
+            for seg in 1..8 {
+                assert_eq!(
+                    rngs_aln(seg, lim, 1).collect::<Vec<Range<usize>>>(),
+                    rngs(seg, lim).collect::<Vec<Range<usize>>>()
+                );
+                for rng in rngs_aln(seg, lim, 4).rev().skip(1) {
+                    assert_eq!(rng.end % 4, 0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rngs_aln_byt_n() {
+        assert_eq!(
+            rngs_aln_byt(2, 32, 64, 4).collect::<Vec<Range<usize>>>(),
+            [0..16, 16..32]
+        );
+        assert_eq!(
+            rngs_aln_byt(2, 32, 64, 24).collect::<Vec<Range<usize>>>(),
+            [0..16, 16..32]
+        );
+        assert_eq!(
+            rngs_aln_byt(4, 32, 64, 128).collect::<Vec<Range<usize>>>(),
+            rngs(4, 32).collect::<Vec<Range<usize>>>()
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn rngs_par_n() {
+        use rayon::prelude::*;
+        assert_eq!(
+            rngs(4, 10).into_par_iter().collect::<Vec<Range<usize>>>(),
+            [0..3, 3..6, 6..8, 8..10]
+        );
+        assert_eq!(rngs(1000, 100_000).into_par_iter().len(), 1000);
+        assert_eq!(
+            rngs(1000, 100_000)
+                .into_par_iter()
+                .map(|rng| rng.len())
+                .sum::<usize>(),
+            100_000
+        );
+        assert_eq!(
+            rngs_rng(997, -50_000i64..50_000)
+                .into_par_iter()
+                .with_min_len(3)
+                .collect::<Vec<Range<i64>>>(),
+            rngs_rng(997, -50_000i64..50_000).collect::<Vec<Range<i64>>>()
+        );
+        let mut itr = rngs(8, 80);
+        itr.next();
+        itr.next_back();
+        assert_eq!(
+            itr.clone()
+                .into_par_iter()
+                .rev()
+                .collect::<Vec<Range<usize>>>(),
+            itr.rev().collect::<Vec<Range<usize>>>()
+        );
+    }
+
+    #[test]
+    fn par_map_rng_n() {
+        assert_eq!(par_map_rng(4, 10, |rng| rng.len()), [3, 3, 2, 2]);
+        assert_eq!(
+            par_map_rng(16, 1000, |rng| rng),
+            rngs(16, 1000).collect::<Vec<Range<usize>>>()
+        );
+        assert_eq!(par_map_rng(3, 0, |rng| rng.len()), [0]);
+    }
+
+    #[test]
+    fn par_for_each_rng_n() {
+        let sum = std::sync::atomic::AtomicUsize::new(0);
+        par_for_each_rng(8, 100, |rng| {
+            sum.fetch_add(rng.sum::<usize>(), std::sync::atomic::Ordering::Relaxed);
+        });
+        assert_eq!(sum.into_inner(), (0..100).sum::<usize>());
+    }
+
+    #[test]
+    #[should_panic(expected = "rngs segment 2 panicked: bad segment 6..8")]
+    fn par_map_rng_p() {
+        par_map_rng(4, 10, |rng| {
+            if rng.start == 6 {
+                panic!("bad segment {rng:?}");
+            }
+            rng.len()
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "rngs segment 1 panicked: bad")]
+    fn par_for_each_rng_p() {
+        par_for_each_rng(3, 3, |rng| assert!(rng.start != 1, "bad"));
+    }
+
+    #[test]
+    fn split_mut_by_n() {
+        let mut vals = [0u8; 10];
+        let mut itr = split_mut_by(&mut vals, rngs(4, 10));
+        assert_eq!(itr.len(), 4);
+        for (rng, sl) in itr.by_ref().take(2) {
+            assert_eq!(rng.len(), sl.len());
+            sl.fill(rng.start as u8);
+        }
+        for (rng, sl) in itr {
+            sl.fill(rng.end as u8);
+        }
+        assert_eq!(vals, [0, 0, 0, 3, 3, 3, 8, 8, 10, 10]);
+
+        let mut vals: Vec<usize> = vec![0; 16];
+        thread::scope(|scp| {
+            for (rng, sl) in vals.split_mut_by(rngs_weighted(&[2, 1, 1], 16)) {
+                scp.spawn(move || sl.iter_mut().for_each(|val| *val = rng.start));
+            }
+        });
+        assert_eq!(vals[..8], [0; 8]);
+        assert_eq!(vals[8..12], [8; 4]);
+        assert_eq!(vals[12..], [12; 4]);
+
+        let mut vals = [0u8; 50];
+        for (idx, (_, sl)) in vals.split_mut_by(rngs_aln(3, 50, 16)).enumerate() {
+            sl.fill(idx as u8);
+        }
+        assert_eq!(vals[31..33], [0, 1]);
+        assert_eq!(vals[47..49], [1, 2]);
+
+        let mut vals = [1, 2, 3, 4, 5, 6];
+        let sls: Vec<_> = vals
+            .split_mut_by([1..2, 2..2, 4..6])
+            .map(|(_, sl)| sl.to_vec())
+            .collect();
+        assert_eq!(sls, [vec![2], vec![], vec![5, 6]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "not ascending and disjoint")]
+    fn split_mut_by_ovr_p() {
+        let mut vals = [0; 6];
+        split_mut_by(&mut vals, [0..3, 2..6]).for_each(drop);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn split_mut_by_oob_p() {
+        let mut vals = [0; 6];
+        split_mut_by(&mut vals, rngs(2, 7)).for_each(drop);
+    }
+
+    #[test]
+    fn prt_n() {
+        let prt = prt(4, 10);
+        assert_eq!(prt.len(), 4);
+        assert!(!prt.is_empty());
+        assert_eq!(prt.get(0), Some(0..3));
+        assert_eq!(prt.get(2), Some(6..8));
+        assert_eq!(prt.get(3), Some(8..10));
+        assert_eq!(prt.get(4), None);
+        assert!(prt.contains(0));
+        assert!(prt.contains(9));
+        assert!(!prt.contains(10));
+        assert_eq!(
+            (0..11).map(|val| prt.segment_of(val)).collect::<Vec<_>>(),
+            [
+                Some(0),
+                Some(0),
+                Some(0),
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(2),
+                Some(2),
+                Some(3),
+                Some(3),
+                None
+            ]
+        );
+        assert_eq!(
+            prt.iter().collect::<Vec<Range<usize>>>(),
+            [0..3, 3..6, 6..8, 8..10]
+        );
+        assert_eq!(prt.into_iter().len(), 4);
+    }
+
+    #[test]
+    fn prt_segment_of_n() {
+        for lim in 0..30 {
+            for seg in 1..12 {
+                for prt in [
+                    prt(seg, lim),
+                    try_rngs_with(seg, lim, SegPolicy::AllowEmpty)
+                        .unwrap()
+                        .prt(),
+                ] {
+                    for (idx, rng) in prt.iter().enumerate() {
+                        assert_eq!(prt.get(idx), Some(rng.clone()));
+                        for val in rng {
+                            assert_eq!(prt.segment_of(val), Some(idx));
+                        }
+                    }
+                    assert_eq!(prt.segment_of(lim), None);
+                }
+            }
+        }
+        let prt = rngs_rng(3, -6i64..6).prt();
+        assert_eq!(prt.segment_of(-7), None);
+        assert_eq!(prt.segment_of(-6), Some(0));
+        assert_eq!(prt.segment_of(-2), Some(1));
+        assert_eq!(prt.segment_of(5), Some(2));
+        assert_eq!(prt.segment_of(6), None);
+        let prt = rngs_rng(7, 0u128..u128::MAX).prt();
+        assert_eq!(prt.segment_of(u128::MAX - 1), Some(6));
+        assert_eq!(prt.segment_of(u128::MAX), None);
+        assert_eq!(prt.get(6).map(|rng| rng.end), Some(u128::MAX));
+    }
+
+    #[test]
+    fn rngs_prt_n() {
+        let mut itr = rngs(4, 10);
+        itr.next();
+        itr.next_back();
+        assert_eq!(itr.prt(), prt(4, 10));
+        assert_eq!(itr.prt().len(), 4);
+    }
+
+    #[test]
+    fn rngs_rem_n() {
+        assert_eq!(
+            rngs(4, 10)
+                .rem_pol(RemainderPolicy::Front)
+                .collect::<Vec<Range<usize>>>(),
+            [0..3, 3..6, 6..8, 8..10]
+        );
+        assert_eq!(
+            rngs(4, 10)
+                .rem_pol(RemainderPolicy::Back)
+                .collect::<Vec<Range<usize>>>(),
+            [0..2, 2..4, 4..7, 7..10]
+        );
+        assert_eq!(
+            rngs(4, 10)
+                .rem_pol(RemainderPolicy::Spread)
+                .collect::<Vec<Range<usize>>>(),
+            [0..2, 2..5, 5..7, 7..10]
+        );
+        assert_eq!(
+            rngs(4, 10)
+                .rem_pol(RemainderPolicy::Last)
+                .collect::<Vec<Range<usize>>>(),
+            [0..2, 2..4, 4..6, 6..10]
+        );
+        assert_eq!(
+            rngs(3, 11)
+                .rem_pol(RemainderPolicy::Spread)
+                .collect::<Vec<Range<usize>>>(),
+            [0..3, 3..7, 7..11]
+        );
+        assert_eq!(
+            try_rngs_with(4, 2, SegPolicy::AllowEmpty)
+                .unwrap()
+                .rem_pol(RemainderPolicy::Spread)
+                .collect::<Vec<Range<usize>>>(),
+            [0..0, 0..1, 1..1, 1..2]
+        );
+        assert_eq!(
+            rngs(4, 10)
+                .rem_pol(RemainderPolicy::Back)
+                .rev()
+                .collect::<Vec<Range<usize>>>(),
+            [7..10, 4..7, 2..4, 0..2]
+        );
+    }
+
+    #[test]
+    fn prt_rem_n() {
+        let pols = [
+            RemainderPolicy::Front,
+            RemainderPolicy::Back,
+            RemainderPolicy::Spread,
+            RemainderPolicy::Last,
+        ];
+        for lim in 0..30 {
+            for seg in 1..12 {
+                for pol in pols {
+                    for prt in [
+                        prt(seg, lim).rem_pol(pol),
+                        try_rngs_with(seg, lim, SegPolicy::AllowEmpty)
+                            .unwrap()
+                            .prt()
+                            .rem_pol(pol),
+                    ] {
+                        let mut end = 0;
+                        for (idx, rng) in prt.iter().enumerate() {
+                            assert_eq!(rng.start, end);
+                            end = rng.end;
+                            for val in rng {
+                                assert_eq!(prt.segment_of(val), Some(idx));
+                            }
+                        }
+                        assert_eq!(end, lim);
+                        assert_eq!(prt.segment_of(lim), None);
+                    }
+                }
+            }
+        }
+        for pol in pols {
+            let prt = rngs_rng(7, 0u128..u128::MAX).prt().rem_pol(pol);
+            assert_eq!(prt.get(6).map(|rng| rng.end), Some(u128::MAX));
+            for idx in 0..7 {
+                let rng = prt.get(idx).unwrap();
+                assert_eq!(prt.segment_of(rng.start), Some(idx));
+                assert_eq!(prt.segment_of(rng.end - 1), Some(idx));
+            }
+        }
+    }
+
+    #[test]
+    fn rngs_rng_inc_rem_n() {
+        let itr = |pol| rngs_rng_inc(3, 0u64..=u64::MAX).rem_pol(pol);
+        assert_eq!(
+            itr(RemainderPolicy::Last).collect::<Vec<RangeInclusive<u64>>>(),
+            [
+                0..=0x5555_5555_5555_5554,
+                0x5555_5555_5555_5555..=0xaaaa_aaaa_aaaa_aaa9,
+                0xaaaa_aaaa_aaaa_aaaa..=u64::MAX
+            ]
+        );
+        for pol in [RemainderPolicy::Back, RemainderPolicy::Spread] {
+            assert_eq!(
+                itr(pol).collect::<Vec<RangeInclusive<u64>>>(),
+                itr(RemainderPolicy::Last).collect::<Vec<RangeInclusive<u64>>>()
+            );
+        }
+        let itr = rngs_rng_inc(1, i128::MIN..=i128::MAX).rem_pol(RemainderPolicy::Last);
+        assert_eq!(
+            itr.collect::<Vec<RangeInclusive<i128>>>(),
+            vec![i128::MIN..=i128::MAX; 1]
+        );
+    }
+
+    #[test]
+    fn chunk_dispenser_n() {
+        let dsp = ChunkDispenser::new(10, 2, ChunkSchedule::Fixed(4));
+        assert_eq!(dsp.rem(), 10);
+        assert_eq!(dsp.take_chunk(), Some(0..4));
+        assert_eq!(dsp.rem(), 6);
+        assert_eq!(dsp.collect::<Vec<Range<usize>>>(), [4..8, 8..10]);
+        assert_eq!(dsp.take_chunk(), None);
+        assert_eq!(dsp.rem(), 0);
+        assert_eq!(
+            ChunkDispenser::new(100, 4, ChunkSchedule::Guided(10)).collect::<Vec<Range<usize>>>(),
+            [
+                0..25,
+                25..44,
+                44..58,
+                58..69,
+                69..79,
+                79..89,
+                89..99,
+                99..100
+            ]
+        );
+        assert_eq!(
+            ChunkDispenser::new(100, 4, ChunkSchedule::Guided(0)).collect::<Vec<Range<usize>>>(),
+            [
+                0..25,
+                25..44,
+                44..58,
+                58..69,
+                69..77,
+                77..83,
+                83..88,
+                88..91,
+                91..94,
+                94..96,
+                96..97,
+                97..98,
+                98..99,
+                99..100
+            ]
+        );
+        assert_eq!(
+            ChunkDispenser::new(100, 2, ChunkSchedule::Factoring(8)).collect::<Vec<Range<usize>>>(),
+            [0..25, 25..50, 50..63, 63..76, 76..84, 84..92, 92..100]
+        );
+        assert_eq!(
+            ChunkDispenser::new(100, 4, ChunkSchedule::Factoring(0))
+                .take(9)
+                .collect::<Vec<Range<usize>>>(),
+            [
+                0..13,
+                13..26,
+                26..39,
+                39..52,
+                52..58,
+                58..64,
+                64..70,
+                70..76,
+                76..79
+            ]
+        );
+        assert_eq!(
+            ChunkDispenser::new(0, 1, ChunkSchedule::Fixed(4)).take_chunk(),
+            None
+        );
+        let dsp = ChunkDispenser::new(usize::MAX, 1, ChunkSchedule::Fixed(usize::MAX / 2 + 1));
+        assert_eq!(
+            dsp.collect::<Vec<Range<usize>>>(),
+            [0..usize::MAX / 2 + 1, usize::MAX / 2 + 1..usize::MAX]
+        );
+    }
+
+    #[test]
+    fn chunk_dispenser_thd_n() {
+        for sch in [
+            ChunkSchedule::Fixed(7),
+            ChunkSchedule::Guided(3),
+            ChunkSchedule::Factoring(1),
+        ] {
+            let dsp = ChunkDispenser::new(10_000, 4, sch);
+            let mut rngs: Vec<Range<usize>> = thread::scope(|scp| {
+                let hnds: Vec<_> = (0..4)
+                    .map(|_| scp.spawn(|| (&dsp).collect::<Vec<Range<usize>>>()))
+                    .collect();
+                hnds.into_iter()
+                    .flat_map(|hnd| hnd.join().unwrap())
+                    .collect()
+            });
+            rngs.sort_by_key(|rng| rng.start);
+            assert_eq!(
+                rngs,
+                ChunkDispenser::new(10_000, 4, sch).collect::<Vec<Range<usize>>>()
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn chunk_dispenser_zro_p() {
+        ChunkDispenser::new(10, 2, ChunkSchedule::Fixed(0));
+    }
+
+    #[test]
+    fn rng_tree_n() {
+        let tree = bisect(2, 7);
+        assert_eq!(tree.root(), 0..7);
+        assert_eq!(tree.depth(), 2);
+        assert_eq!(
+            tree.children(&(0..7))
+                .unwrap()
+                .collect::<Vec<Range<usize>>>(),
+            [0..4, 4..7]
+        );
+        assert_eq!(
+            tree.children(&(4..7))
+                .unwrap()
+                .collect::<Vec<Range<usize>>>(),
+            [4..6, 6..7]
+        );
+        assert!(tree.children(&(6..7)).is_none());
+        assert!(tree.is_leaf(&(0..2)));
+        assert_eq!(tree.parent(&(6..7)), Some(4..7));
+        assert_eq!(tree.parent(&(0..4)), Some(0..7));
+        assert_eq!(tree.parent(&(0..7)), None);
+        assert_eq!(tree.parent(&(1..3)), None);
+        assert_eq!(tree.depth_of(&(0..7)), Some(0));
+        assert_eq!(tree.depth_of(&(4..6)), Some(2));
+        assert_eq!(tree.depth_of(&(4..5)), None);
+        assert_eq!(
+            tree.leaves(TreeOrd::Dfs).collect::<Vec<Range<usize>>>(),
+            [0..2, 2..4, 4..6, 6..7]
+        );
+    }
+
+    #[test]
+    fn rng_tree_leaves_n() {
+        let tree = bisect(2, 5);
+        assert_eq!(tree.depth(), 2);
+        assert_eq!(
+            tree.leaves(TreeOrd::Dfs).collect::<Vec<Range<usize>>>(),
+            [0..2, 2..3, 3..5]
+        );
+        assert_eq!(
+            tree.leaves(TreeOrd::Bfs).collect::<Vec<Range<usize>>>(),
+            [3..5, 0..2, 2..3]
+        );
+        let tree = rng_tree(3, 4, 100);
+        assert_eq!(tree.depth(), 3);
+        let lvs: Vec<Range<usize>> = tree.leaves(TreeOrd::Dfs).collect();
+        assert_eq!(lvs.len(), 27);
+        assert_eq!(lvs.first(), Some(&(0..4)));
+        assert_eq!(lvs.last(), Some(&(97..100)));
+        assert!(lvs.windows(2).all(|win| win[0].end == win[1].start));
+        assert!(lvs
+            .iter()
+            .all(|lf| tree.is_leaf(lf) && tree.depth_of(lf).is_some()));
+        let mut bfs: Vec<Range<usize>> = tree.leaves(TreeOrd::Bfs).collect();
+        bfs.sort_by_key(|lf| lf.start);
+        assert_eq!(bfs, lvs);
+        let mut itr = rng_tree(2, 1, 0).leaves(TreeOrd::Bfs);
+        assert_eq!(itr.next(), Some(0..0));
+        assert_eq!(itr.next(), None);
+    }
+
+    #[test]
+    fn rngs_dlm_n() {
+        assert_eq!(
+            rngs_dlm(2, b"ab\ncd\nef\n", b'\n').collect::<Vec<Range<usize>>>(),
+            [0..6, 6..9]
+        );
+        assert_eq!(
+            rngs_dlm(3, b"ab\ncd\nef\n", b'\n').collect::<Vec<Range<usize>>>(),
+            [0..3, 3..6, 6..9]
+        );
+        assert_eq!(
+            rngs_dlm(3, b"abcdefgh\ni\n", b'\n').collect::<Vec<Range<usize>>>(),
+            [0..9, 9..11]
+        );
+        assert_eq!(
+            rngs_dlm(2, b"ab\ncd", b'\n').collect::<Vec<Range<usize>>>(),
+            [0..3, 3..5]
+        );
+        assert_eq!(
+            rngs_dlm(4, b"abcdefgh", b'\n').collect::<Vec<Range<usize>>>(),
+            vec![0..8; 1]
+        );
+        assert_eq!(rngs_dlm(4, b"", b'\n').len(), 0);
+        assert_eq!(
+            rngs_dlm(8, b"\n\n\n", b'\n').collect::<Vec<Range<usize>>>(),
+            [0..1, 1..2, 2..3]
+        );
+        assert_eq!(
+            rngs_dlm_by(2, b"a,b;c,d", |byt| byt == b',' || byt == b';')
+                .collect::<Vec<Range<usize>>>(),
+            [0..4, 4..7]
+        );
+    }
+
+    #[test]
+    fn rngs_dlm_rdr_n() {
+        use std::io::Cursor;
+        let mut buf = Vec::new();
+        for idx in 0..5000 {
+            buf.extend_from_slice(format!("{}\n", "x".repeat(idx % 97)).as_bytes());
+        }
+        buf.extend_from_slice(&[b'y'; 20_000]);
+        for seg in [1, 2, 7, 64, 1000] {
+            let rngs = rngs_dlm(seg, &buf, b'\n');
+            assert_eq!(
+                rngs_dlm_rdr(seg, &mut Cursor::new(&buf), b'\n')
+                    .unwrap()
+                    .collect::<Vec<Range<usize>>>(),
+                rngs.clone().collect::<Vec<Range<usize>>>()
+            );
+            let mut end = 0;
+            for rng in rngs {
+                assert_eq!(rng.start, end);
+                assert!(rng.start == 0 || buf[rng.start - 1] == b'\n');
+                end = rng.end;
+            }
+            assert_eq!(end, buf.len());
+        }
+        assert_eq!(
+            rngs_dlm_rdr(3, &mut Cursor::new(b""), b'\n').unwrap().len(),
+            0
+        );
+        assert_eq!(
+            rngs_dlm_rdr(3, &mut Cursor::new(b"abcdefgh\ni\n"), b'\n')
+                .unwrap()
+                .collect::<Vec<Range<usize>>>(),
+            [0..9, 9..11]
+        );
+    }
+
+    #[test]
+    fn rngs_dlm_rdr_intr_n() {
+        use std::io::Cursor;
+        /// A reader whose first read is interrupted.
+        struct IntrRdr<'a> {
+            cur: Cursor<&'a [u8]>,
+            intr: bool,
+        }
+        impl Read for IntrRdr<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                if mem::take(&mut self.intr) {
+                    return Err(io::ErrorKind::Interrupted.into());
+                }
+                self.cur.read(buf)
+            }
+        }
+        impl Seek for IntrRdr<'_> {
+            fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+                self.cur.seek(pos)
+            }
+        }
+        let buf = b"abcdefgh\ni\n";
+        let mut rdr = IntrRdr {
+            cur: Cursor::new(buf),
+            intr: true,
+        };
+        assert_eq!(
+            rngs_dlm_rdr(3, &mut rdr, b'\n')
+                .unwrap()
+                .collect::<Vec<Range<usize>>>(),
+            [0..9, 9..11]
+        );
+        assert!(!rdr.intr);
+    }
+
+    #[test]
+    fn rngs_str_n() {
+        assert_eq!(
+            rngs_str(3, "€€€€", StrBnd::Char).collect::<Vec<Range<usize>>>(),
+            [0..6, 6..9, 9..12]
+        );
+        assert_eq!(
+            rngs_str(2, "aé€b", StrBnd::Char).collect::<Vec<Range<usize>>>(),
+            [0..6, 6..7]
+        );
+        assert_eq!(
+            rngs_str(2, "ab cd ef", StrBnd::Ws).collect::<Vec<Range<usize>>>(),
+            [0..6, 6..8]
+        );
+        assert_eq!(
+            rngs_str(3, "ab, cd, ef", StrBnd::Word).collect::<Vec<Range<usize>>>(),
+            [0..4, 4..8, 8..10]
+        );
+        assert_eq!(rngs_str(3, "", StrBnd::Char).len(), 0);
+        assert_eq!(
+            rngs_str(4, "abcdefgh", StrBnd::Ws).collect::<Vec<Range<usize>>>(),
+            vec![0..8; 1]
+        );
+        assert_eq!(
+            rngs_str(4, "€€", StrBnd::Char).collect::<Vec<Range<usize>>>(),
+            [0..3, 3..6]
+        );
+        let s = "Grüße, 世界! Ωμέγα 🦀🦀 crab\tdone\u{301}\n".repeat(20);
+        for bnd in [StrBnd::Char, StrBnd::Ws, StrBnd::Word] {
+            for seg in 1..40 {
+                let mut end = 0;
+                for rng in rngs_str(seg, &s, bnd) {
+                    assert_eq!(rng.start, end);
+                    assert!(!s[rng.clone()].is_empty());
+                    assert!(!s[rng.clone()].starts_with(is_mrk));
+                    end = rng.end;
+                }
+                assert_eq!(end, s.len());
+            }
+        }
+    }
+
+    #[test]
+    fn strs_n() {
+        assert_eq!(
+            strs(3, "€€€€", StrBnd::Char).collect::<Vec<&str>>(),
+            ["€€", "€", "€"]
+        );
+        assert_eq!(
+            strs(2, "ab cd ef", StrBnd::Ws).collect::<Vec<&str>>(),
+            ["ab cd ", "ef"]
+        );
+        assert_eq!(
+            strs(3, "  ab  cd  ", StrBnd::Ws)
+                .rev()
+                .collect::<Vec<&str>>(),
+            ["cd  ", "  ab  "]
+        );
+        assert_eq!(
+            strs(5, "🦀🦀🦀", StrBnd::Char).collect::<String>(),
+            "🦀🦀🦀"
+        );
+        assert_eq!(
+            strs(3, "ab \u{301}cd \u{301}ef", StrBnd::Ws).collect::<Vec<&str>>(),
+            ["ab \u{301}", "cd \u{301}", "ef"]
+        );
+        assert_eq!(
+            strs(4, "e\u{301}e\u{301}", StrBnd::Char).collect::<Vec<&str>>(),
+            ["e\u{301}", "e\u{301}"]
+        );
+        assert_eq!(
+            strs(3, "ne\u{301}e, ok", StrBnd::Word).collect::<Vec<&str>>(),
+            ["ne\u{301}e", ", ", "ok"]
+        );
+    }
+
+    #[test]
+    fn cyc_n() {
+        let dst = cyc(3, 8);
+        assert_eq!(dst.wkrs(), 3);
+        assert_eq!(dst.idxs(0).collect::<Vec<usize>>(), [0, 3, 6]);
+        assert_eq!(dst.idxs(1).collect::<Vec<usize>>(), [1, 4, 7]);
+        assert_eq!(dst.idxs(2).collect::<Vec<usize>>(), [2, 5]);
+        assert_eq!(dst.owner(7), Some(1));
+        assert_eq!(dst.owner(8), None);
+        assert_eq!(cyc(4, 2).rngs(3).len(), 0);
+    }
+
+    #[test]
+    fn blk_cyc_n() {
+        let dst = blk_cyc(2, 3, 10);
+        assert_eq!(dst.rngs(0).collect::<Vec<Range<usize>>>(), [0..3, 6..9]);
+        assert_eq!(dst.rngs(1).collect::<Vec<Range<usize>>>(), [3..6, 9..10]);
+        assert_eq!(
+            dst.rngs(1).rev().collect::<Vec<Range<usize>>>(),
+            [9..10, 3..6]
+        );
+        assert_eq!(dst.rngs(1).nth(1), Some(9..10));
+        for (seg, blk, lim) in [(1, 1, 0), (3, 2, 17), (4, 5, 20), (5, 4, 3), (7, 3, 100)] {
+            let dst = blk_cyc(seg, blk, lim);
+            let mut own = vec![usize::MAX; lim];
+            for wkr in 0..seg {
+                assert_eq!(dst.rngs(wkr).len(), dst.rngs(wkr).count());
+                for idx in dst.idxs(wkr) {
+                    assert_eq!(own[idx], usize::MAX);
+                    own[idx] = wkr;
+                }
+            }
+            for (idx, wkr) in own.into_iter().enumerate() {
+                assert_eq!(dst.owner(idx), Some(wkr));
+            }
+        }
+        let dst = blk_cyc(2, usize::MAX / 2 + 1, usize::MAX);
+        assert_eq!(
+            dst.rngs(1).collect::<Vec<Range<usize>>>(),
+            vec![usize::MAX / 2 + 1..usize::MAX; 1]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn blk_cyc_wkr_p() {
+        cyc(3, 8).rngs(3);
+    }
+
+    #[test]
+    fn rng_for_rank_n() {
+        for (size, lim) in [(1, 0), (3, 10), (4, 3), (7, 100), (5, usize::MAX)] {
+            for rank in 0..size + 1 {
+                assert_eq!(rng_for_rank(rank, size, lim), rngs(size, lim).nth(rank));
+            }
+        }
+        assert_eq!(rng_for_ranks(&[1, 0], &[2, 3], 10), Some(5..7));
+        assert_eq!(rng_for_ranks(&[1, 2], &[2, 3], 10), Some(9..10));
+        assert_eq!(rng_for_ranks(&[0, 2], &[2, 8], 4), None);
+        assert_eq!(rng_for_ranks(&[], &[], 4), Some(0..4));
+        let all: Vec<Range<usize>> = (0..2)
+            .flat_map(|nod| (0..3).map(move |thd| rng_for_ranks(&[nod, thd], &[2, 3], 20).unwrap()))
+            .collect();
+        assert_eq!(all.first().unwrap().start, 0);
+        assert_eq!(all.last().unwrap().end, 20);
+        assert!(all.windows(2).all(|w| w[0].end == w[1].start));
+    }
+
+    #[test]
+    fn rng_for_rank_env_n() {
+        // The environment is only read, as tests run on many threads.
+        let val = || Ok(" 1\n".to_string());
+        assert_eq!(rng_for_rank_val(val(), 3, 10), Ok(4..7));
+        assert_eq!(
+            rng_for_rank_val(val(), 1, 10),
+            Err(RankError::Rank { rank: 1, size: 1 })
+        );
+        assert!(matches!(
+            rng_for_rank_val(Ok("x".to_string()), 3, 10),
+            Err(RankError::Parse(_))
+        ));
+        assert_eq!(
+            rng_for_rank_val(Err(env::VarError::NotPresent), 3, 10),
+            Err(RankError::Var(env::VarError::NotPresent))
+        );
+        assert_eq!(
+            rng_for_rank_env("ITR_TST_RANK_UNSET", 3, 10),
+            Err(RankError::Var(env::VarError::NotPresent))
+        );
+    }
+
+    #[test]
+    fn rngs_min_n() {
+        let itr = rngs_min(64, 10, 100);
+        assert_eq!(itr.len(), 10);
+        assert!(itr.clone().all(|rng| rng.len() == 10));
+        assert_eq!(
+            rngs_min(4, 3, 10).collect::<Vec<Range<usize>>>(),
+            [0..4, 4..7, 7..10]
+        );
+        assert_eq!(
+            rngs_min(4, 3, 2).collect::<Vec<Range<usize>>>(),
+            vec![0..2; 1]
+        );
+        assert_eq!(
+            rngs_min(4, 3, 0).collect::<Vec<Range<usize>>>(),
+            vec![0..0; 1]
+        );
+        assert_eq!(rngs_min(4, 0, 10).len(), 4);
+        for (seg, min, lim) in [(8, 3, 17), (5, 7, 34), (3, 1, 2), (9, 4, 100)] {
+            let itr = rngs_min(seg, min, lim);
+            assert!(itr.len() <= seg);
+            assert!(itr.clone().all(|rng| rng.len() >= min.min(lim)));
+            assert_eq!(itr.clone().last().unwrap().end, lim);
+        }
+        let itr = rngs_rng(8, 10u8..30)
+            .rem_pol(RemainderPolicy::Last)
+            .min_len(6);
+        assert_eq!(itr.collect::<Vec<Range<u8>>>(), [10..16, 16..22, 22..30]);
+    }
+
+    #[test]
+    fn nested_rngs_n() {
+        assert_eq!(
+            nested_rngs(&[2, 2], 10).collect::<Vec<([usize; 2], Range<usize>)>>(),
+            [
+                ([0, 0], 0..3),
+                ([0, 1], 3..5),
+                ([1, 0], 5..8),
+                ([1, 1], 8..10)
+            ]
+        );
+        assert_eq!(
+            nested_rngs(&[2, 3], 4).collect::<Vec<([usize; 2], Range<usize>)>>(),
+            [
+                ([0, 0], 0..1),
+                ([0, 1], 1..2),
+                ([1, 0], 2..3),
+                ([1, 1], 3..4)
+            ]
+        );
+        assert_eq!(
+            nested_rngs(&[2, 2], 10)
+                .lvl(0)
+                .collect::<Vec<([usize; 2], Range<usize>)>>(),
+            [([0, 0], 0..5), ([1, 0], 5..10)]
+        );
+        assert_eq!(
+            nested_rngs(&[3], 7).collect::<Vec<([usize; 1], Range<usize>)>>(),
+            [([0], 0..3), ([1], 3..5), ([2], 5..7)]
+        );
+        let mut itr = nested_rngs(&[2, 4, 8], 1000);
+        let lvs: Vec<([usize; 3], Range<usize>)> = itr.by_ref().collect();
+        assert_eq!(itr.next(), None);
+        assert_eq!(lvs.len(), 64);
+        assert_eq!(lvs.first().unwrap().1.start, 0);
+        assert_eq!(lvs.last().unwrap(), &([1, 3, 7], 985..1000));
+        assert!(lvs
+            .windows(2)
+            .all(|w| w[0].1.end == w[1].1.start && w[0].0 < w[1].0));
+        for (pth, rng) in nested_rngs(&[2, 4, 8], 1000).lvl(1) {
+            assert_eq!(pth[2], 0);
+            let prn = rngs(2, 1000).nth(pth[0]).unwrap();
+            assert_eq!(Some(rng), rngs_rng(4, prn).nth(pth[1]));
+        }
+    }
+
+    #[test]
+    fn cursor_rng_n() {
+        let mut itr = rngs(4, 10);
+        itr.next();
+        let cur = itr.cursor();
+        assert_eq!(cur.to_string(), "1.rng.0.10.4.0.1.4");
+        let cur: Cursor = cur.to_string().parse().unwrap();
+        let rsm = RngItr::resume(&cur).unwrap();
+        assert_eq!(
+            rsm.collect::<Vec<Range<usize>>>(),
+            itr.collect::<Vec<Range<usize>>>()
+        );
+        let mut itr = rngs_rng(5, -100i16..900)
+            .rem_pol(RemainderPolicy::Spread)
+            .min_len(300);
+        itr.next_back();
+        let rsm = RngItr::<i16>::resume(&itr.cursor()).unwrap();
+        assert_eq!(rsm.len(), 2);
+        assert!(rsm.eq(itr));
+        let itr = rngs_rng(3, u64::MAX - 5..u64::MAX);
+        assert!(RngItr::<u64>::resume(&itr.cursor())
+            .unwrap()
+            .eq(itr.clone()));
+        assert_eq!(
+            RngItr::<u8>::resume(&itr.cursor()).err(),
+            Some(CursorError::Val)
+        );
+        assert_eq!(
+            RngItr::<usize>::resume(&"1.rng.0.10.4.0.5.4".parse().unwrap()).err(),
+            Some(CursorError::Val)
+        );
+        assert_eq!(
+            RngItr::<usize>::resume(&"1.rng.0.10.0.0.0.0".parse().unwrap()).err(),
+            Some(CursorError::Val)
+        );
+        assert_eq!(
+            RngItr::<usize>::resume(&"1.cyc.3.1.8.0.0.3".parse().unwrap()).err(),
+            Some(CursorError::Knd)
+        );
+        assert_eq!("2.rng.0".parse::<Cursor>(), Err(CursorError::Ver(2)));
+        assert_eq!("1.foo.0".parse::<Cursor>(), Err(CursorError::Fmt));
+        assert_eq!("1.rng.x".parse::<Cursor>(), Err(CursorError::Fmt));
+        assert_eq!("".parse::<Cursor>(), Err(CursorError::Fmt));
+    }
+
+    #[test]
+    fn cursor_other_n() {
+        let mut itr = rngs_rng_inc(3, u128::MIN..=u128::MAX);
+        itr.next();
+        let rsm = RngIncItr::<u128>::resume(&itr.cursor()).unwrap();
+        assert!(rsm.eq(itr));
+        let itr = rngs_rng_inc(1, 0u64..=u64::MAX);
+        assert!(RngIncItr::<u64>::resume(&itr.cursor())
+            .unwrap()
+            .eq(itr.clone()));
+        #[allow(clippy::reversed_empty_ranges)]
+        let itr = rngs_rng_inc(2, 5i8..=4);
+        assert_eq!(RngIncItr::<i8>::resume(&itr.cursor()).unwrap().count(), 0);
+
+        let mut itr = rngs_weighted(&[1, 2, 3], 12);
+        itr.next();
+        let cur = itr.cursor();
+        assert_eq!(cur.to_string(), "1.bnd.2.6.12");
+        assert!(BndItr::resume(&cur).unwrap().eq(itr.clone()));
+        itr.by_ref().for_each(drop);
+        assert_eq!(BndItr::resume(&itr.cursor()).unwrap().next(), None);
+        assert_eq!(
+            BndItr::resume(&"1.bnd.4.2".parse().unwrap()).err(),
+            Some(CursorError::Val)
+        );
+
+        let mut itr = blk_cyc(2, 3, 10).rngs(1);
+        itr.next();
+        assert!(BlkCycItr::resume(&itr.cursor()).unwrap().eq(itr.clone()));
+        assert_eq!(
+            BlkCycItr::resume(&"1.cyc.2.3.10.2.0.0".parse().unwrap()).err(),
+            Some(CursorError::Val)
+        );
+
+        let mut itr = tiles_ord(&[2, 3], &[4, 6], TileOrd::ColMaj);
+        itr.nth(2);
+        let rsm = TileItr::<2>::resume(&itr.cursor()).unwrap();
+        assert!(rsm.eq(itr.clone()));
+        assert_eq!(
+            TileItr::<3>::resume(&itr.cursor()).err(),
+            Some(CursorError::Val)
+        );
+
+        for ord in [TreeOrd::Dfs, TreeOrd::Bfs] {
+            let itr = rng_tree(3, 4, 50).leaves(ord);
+            for skp in 0..=itr.clone().count() {
+                let mut itr = itr.clone();
+                itr.by_ref().take(skp).for_each(drop);
+                let cur = itr.cursor().to_string().parse().unwrap();
+                assert!(LeafItr::resume(&cur).unwrap().eq(itr));
+            }
+        }
+        let mut itr = bisect(2, 5).leaves(TreeOrd::Dfs);
+        itr.next();
+        assert_eq!(itr.cursor().to_string(), "1.leaf.2.2.5.0.2.3.3.5");
+        assert_eq!(
+            LeafItr::resume(&"1.leaf.2.2.5.0.1.3".parse().unwrap()).err(),
+            Some(CursorError::Val)
+        );
+
+        let dsp = ChunkDispenser::new(100, 4, ChunkSchedule::Guided(10));
+        dsp.take_chunk();
+        let cur = dsp.cursor();
+        assert_eq!(cur.to_string(), "1.chunk.100.4.1.10.25");
+        let rsm = ChunkDispenser::resume(&cur).unwrap();
+        assert!(rsm.into_iter().eq(&dsp));
+        assert_eq!(
+            ChunkDispenser::resume(&"1.chunk.10.2.0.0.0".parse().unwrap()).err(),
+            Some(CursorError::Val)
+        );
+    }
+
+    #[test]
+    fn cursor_wrapped_n() {
+        assert_eq!(
+            rngs_rng(3, -5i64..5).cursor().to_string(),
+            "1.rng.340282366920938463463374607431768211451.10.3.0.0.3"
+        );
+
+        let mut itr = rngs_aln(3, 100, 16);
+        itr.next();
+        let cur = itr.cursor();
+        assert_eq!(cur.to_string(), "1.aln.3.100.16.1.3");
+        let rsm = AlnItr::resume(&cur.to_string().parse().unwrap()).unwrap();
+        assert_eq!(rsm.imb(), itr.imb());
+        assert!(rsm.eq(itr));
+        assert_eq!(
+            AlnItr::resume(&"1.aln.9.100.16.0.9".parse().unwrap()).err(),
+            Some(CursorError::Val)
+        );
+
+        let mut itr = rngs_rng(3, -6i64..6).halo(2, 1);
+        itr.next_back();
+        let rsm = HaloItr::<i64>::resume(&itr.cursor()).unwrap();
+        assert!(rsm.eq(itr.clone()));
+        assert_eq!(
+            HaloItr::<i64>::resume(&rngs_rng(3, -6i64..6).cursor()).err(),
+            Some(CursorError::Knd)
+        );
+
+        for lvl in 0..3 {
+            let itr = nested_rngs(&[2, 3, 2], 50).lvl(lvl);
+            for skp in 0..=itr.clone().count() {
+                let mut itr = itr.clone();
+                itr.by_ref().take(skp).for_each(drop);
+                let cur = itr.cursor().to_string().parse().unwrap();
+                let rsm = NestItr::<3>::resume(&cur).unwrap();
+                assert!(rsm.eq(itr));
+            }
+        }
+        assert_eq!(
+            NestItr::<3>::resume(&"1.nest.50.2.2.3.2.0.1".parse().unwrap()).err(),
+            Some(CursorError::Val)
+        );
+        assert_eq!(
+            NestItr::<2>::resume(&nested_rngs(&[2, 3, 2], 50).cursor()).err(),
+            Some(CursorError::Val)
+        );
+
+        let s = "ab, cd, ef";
+        let mut itr = strs(3, s, StrBnd::Ws);
+        itr.next();
+        let rsm = StrItr::resume(s, &itr.cursor()).unwrap();
+        assert!(rsm.eq(itr.clone()));
+        assert_eq!(
+            StrItr::resume("ab", &itr.cursor()).err(),
+            Some(CursorError::Val)
+        );
+        assert_eq!(itr.cursor().to_string(), "1.str.3.1.1.3");
+        assert_eq!(
+            StrItr::resume(s, &"1.str.3.3.1.3".parse().unwrap()).err(),
+            Some(CursorError::Val)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_n() {
+        let mut itr = rngs(4, 10).rem_pol(RemainderPolicy::Back);
+        itr.next();
+        let jsn = serde_json::to_string(&itr).unwrap();
+        assert_eq!(
+            jsn,
+            r#"{"off":0,"lim":10,"seg":4,"rem":"Back","idx":1,"end":4}"#
+        );
+        let rsm: RngItr = serde_json::from_str(&jsn).unwrap();
+        assert!(rsm.eq(itr.clone()));
+        let prt: Partition<i16> = serde_json::from_str(r#"{"off":-5,"lim":10,"seg":3}"#).unwrap();
+        assert_eq!(prt, rngs_rng(3, -5i16..5).prt());
+        assert_eq!(
+            serde_json::to_string(&prt).unwrap(),
+            r#"{"off":-5,"lim":10,"seg":3,"rem":"Front"}"#
+        );
+        assert!(serde_json::from_str::<Partition<u8>>(r#"{"off":200,"lim":100,"seg":3}"#).is_err());
+        assert!(serde_json::from_str::<Partition>(r#"{"off":0,"lim":10,"seg":0}"#).is_err());
+        assert!(
+            serde_json::from_str::<RngItr>(r#"{"off":0,"lim":10,"seg":4,"idx":2,"end":5}"#)
+                .is_err()
+        );
+
+        let dst = blk_cyc(2, 3, 10);
+        let jsn = serde_json::to_string(&dst).unwrap();
+        assert_eq!(jsn, r#"{"seg":2,"blk":3,"lim":10}"#);
+        assert_eq!(serde_json::from_str::<BlkCyc>(&jsn).unwrap(), dst);
+        assert!(serde_json::from_str::<BlkCyc>(r#"{"seg":2,"blk":0,"lim":10}"#).is_err());
+        let tree = rng_tree(3, 4, 100);
+        let jsn = serde_json::to_string(&tree).unwrap();
+        assert_eq!(jsn, r#"{"ary":3,"leaf":4,"lim":100}"#);
+        assert_eq!(serde_json::from_str::<RngTree>(&jsn).unwrap(), tree);
+        assert!(serde_json::from_str::<RngTree>(r#"{"ary":1,"leaf":4,"lim":100}"#).is_err());
+        let cur = itr.cursor();
+        let jsn = serde_json::to_string(&cur).unwrap();
+        assert_eq!(jsn, r#""1.rng.0.10.4.1.1.4""#);
+        assert_eq!(serde_json::from_str::<Cursor>(&jsn).unwrap(), cur);
+        assert_eq!(
+            serde_json::to_string(&ChunkSchedule::Guided(4)).unwrap(),
+            r#"{"Guided":4}"#
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_plans_n() {
+        let mut itr = rngs_rng_inc(3, -5i8..=5).rem_pol(RemainderPolicy::Back);
+        itr.next();
+        let jsn = serde_json::to_string(&itr).unwrap();
+        assert_eq!(
+            jsn,
+            r#"{"lo":-5,"hi":5,"seg":3,"rem":"Back","idx":1,"end":3}"#
+        );
+        assert!(serde_json::from_str::<RngIncItr<i8>>(&jsn).unwrap().eq(itr));
+        let itr = rngs_rng_inc(1, 0u64..=u64::MAX);
+        let jsn = serde_json::to_string(&itr).unwrap();
+        assert!(serde_json::from_str::<RngIncItr<u64>>(&jsn)
+            .unwrap()
+            .eq(itr));
+        #[allow(clippy::reversed_empty_ranges)]
+        let itr = rngs_rng_inc(2, 5u8..=4);
+        let jsn = serde_json::to_string(&itr).unwrap();
+        assert_eq!(
+            serde_json::from_str::<RngIncItr<u8>>(&jsn).unwrap().count(),
+            0
+        );
+        assert!(serde_json::from_str::<RngIncItr<u8>>(
+            r#"{"lo":5,"hi":4,"seg":1,"idx":0,"end":1}"#
+        )
+        .is_err());
+
+        let mut itr = rngs_rng(3, -6i64..6).halo(2, 1);
+        itr.next();
+        let jsn = serde_json::to_string(&itr).unwrap();
+        assert_eq!(
+            jsn,
+            r#"{"off":-6,"lim":12,"seg":3,"rem":"Front","idx":1,"end":3,"lft":2,"rgt":1}"#
+        );
+        assert!(serde_json::from_str::<HaloItr<i64>>(&jsn).unwrap().eq(itr));
+
+        let mut itr = rngs_aln(3, 100, 16);
+        itr.next_back();
+        let jsn = serde_json::to_string(&itr).unwrap();
+        assert_eq!(jsn, r#"{"seg":3,"lim":100,"aln":16,"idx":0,"end":2}"#);
+        assert!(serde_json::from_str::<AlnItr>(&jsn).unwrap().eq(itr));
+        assert!(
+            serde_json::from_str::<AlnItr>(r#"{"seg":3,"lim":100,"aln":0,"idx":0,"end":2}"#)
+                .is_err()
+        );
+
+        let mut itr = tiles_ord(&[2, 3], &[4, 6], TileOrd::ColMaj);
+        itr.next();
+        let jsn = serde_json::to_string(&itr).unwrap();
+        assert_eq!(
+            jsn,
+            r#"{"seg":[2,3],"lim":[4,6],"ord":"ColMaj","idx":1,"end":6}"#
+        );
+        assert!(serde_json::from_str::<TileItr<2>>(&jsn).unwrap().eq(itr));
+        assert!(serde_json::from_str::<TileItr<3>>(&jsn).is_err());
+
+        let mut itr = rngs_weighted(&[1, 2, 3], 12);
+        itr.next();
+        let jsn = serde_json::to_string(&itr).unwrap();
+        assert_eq!(jsn, r#"{"bnds":[2,6,12]}"#);
+        assert!(serde_json::from_str::<BndItr>(&jsn).unwrap().eq(itr));
+        assert!(serde_json::from_str::<BndItr>(r#"{"bnds":[4,2]}"#).is_err());
     }
 
     #[test]